@@ -0,0 +1,113 @@
+// Registry of known answers, keyed by day, part and a hash of the input.
+//
+// The file has one answer per line:
+//
+//     <day> <part> <input hash> <answer>
+//
+// The hash is `PuzzleInput::hash` in hex. Everything after it is the answer,
+// so answers may contain spaces. Blank lines and lines starting with '#' are
+// ignored.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+
+pub const DEFAULT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../answers.txt");
+
+const HEADER: &str = "# Known answers: <day> <part> <input hash> <answer>\n";
+
+pub struct Answers {
+    path: String,
+    known: BTreeMap<(u32, u32, u64), String>,
+}
+
+impl Answers {
+    // Loads the registry at `path`. A missing file is an empty registry.
+    pub fn load(path: &str) -> Result<Answers, String> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(ref error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(format!("couldn't read {}: {}", path, error)),
+        };
+
+        let mut known = BTreeMap::new();
+        for (line_idx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let bad_line = || format!("{}:{}: expected '<day> <part> <input hash> <answer>'",
+                                      path, line_idx + 1);
+            let mut fields = line.splitn(4, ' ');
+            let day = fields.next().and_then(|s| s.parse().ok()).ok_or_else(bad_line)?;
+            let part = fields.next().and_then(|s| s.parse().ok()).ok_or_else(bad_line)?;
+            let hash = fields.next()
+                             .and_then(|s| u64::from_str_radix(s, 16).ok())
+                             .ok_or_else(bad_line)?;
+            let answer = fields.next().ok_or_else(bad_line)?;
+
+            known.insert((day, part, hash), answer.trim().to_string());
+        }
+
+        Ok(Answers { path: path.to_string(), known })
+    }
+
+    pub fn get(&self, day: u32, part: u32, hash: u64) -> Option<&str> {
+        self.known.get(&(day, part, hash)).map(|answer| answer.as_str())
+    }
+
+    pub fn insert(&mut self, day: u32, part: u32, hash: u64, answer: &str) {
+        self.known.insert((day, part, hash), answer.to_string());
+    }
+
+    // Rewrites the whole file, sorted by day and part.
+    pub fn save(&self) -> Result<(), String> {
+        let mut text = String::from(HEADER);
+        for (&(day, part, hash), answer) in &self.known {
+            text.push_str(&format!("{} {} {:016x} {}\n", day, part, hash, answer));
+        }
+        fs::write(&self.path, text).map_err(|error| format!("couldn't write {}: {}", self.path, error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A file in the temp directory, unique to this test run.
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("aoc2015-{}-{}", std::process::id(), name))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn save_and_load() {
+        let path = temp_path("answers-round-trip.txt");
+        let mut answers = Answers::load(&path).unwrap();
+        assert!(answers.get(25, 2, 1).is_none());
+        answers.insert(25, 2, 1, "Merry Christmas!");
+        answers.insert(11, 1, u64::MAX, "cqjxxyzz");
+        answers.save().unwrap();
+
+        let loaded = Answers::load(&path).unwrap();
+        assert_eq!(loaded.get(25, 2, 1), Some("Merry Christmas!"));
+        assert_eq!(loaded.get(11, 1, u64::MAX), Some("cqjxxyzz"));
+        assert_eq!(loaded.known.len(), 2);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn comments_blanks_and_bad_lines() {
+        let path = temp_path("answers-bad-line.txt");
+        fs::write(&path, "# Known answers\n\n  7 1 00000000000000ff  956 \n7 2 ff\n").unwrap();
+        let error = Answers::load(&path).err().unwrap();
+        assert_eq!(error, format!("{}:4: expected '<day> <part> <input hash> <answer>'", path));
+
+        fs::write(&path, "# Known answers\n\n  7 1 00000000000000ff  956 \n").unwrap();
+        assert_eq!(Answers::load(&path).unwrap().get(7, 1, 0xff), Some("956"));
+        fs::remove_file(&path).unwrap();
+    }
+}
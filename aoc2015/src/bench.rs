@@ -0,0 +1,199 @@
+// Times each day's parse, part 1 and part 2 stages over several runs.
+//
+// Every run of a stage records its wall time and the most heap it had
+// allocated at once (on top of whatever was allocated before it started).
+// Results can be printed as a table, or written as JSON or CSV to compare
+// runs across commits.
+
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+
+use aoc_common::{PuzzleInput, Solver};
+
+use crate::alloc;
+use crate::days::Day;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Stage {
+    fn name(self) -> &'static str {
+        match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "part1",
+            Stage::Part2 => "part2",
+        }
+    }
+}
+
+pub struct StageResult {
+    pub day: u32,
+    pub stage: Stage,
+    pub input_hash: u64,
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+    pub peak_bytes: usize,
+}
+
+// Runs `f` once, returning its result, wall time and peak extra heap use.
+fn measure<T, F: FnOnce() -> T>(f: F) -> (T, Duration, usize) {
+    alloc::reset_peak();
+    let baseline = alloc::current();
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+    (result, elapsed, alloc::peak().saturating_sub(baseline))
+}
+
+fn summarize(day: &Day, stage: Stage, input: &PuzzleInput, mut times: Vec<Duration>, peak_bytes: usize) -> StageResult {
+    times.sort();
+    let runs = times.len();
+    let median = if runs.is_multiple_of(2) {
+        (times[runs / 2 - 1] + times[runs / 2]) / 2
+    } else {
+        times[runs / 2]
+    };
+
+    StageResult {
+        day: day.number,
+        stage,
+        input_hash: input.hash(),
+        runs,
+        min: times[0],
+        median,
+        max: times[runs - 1],
+        peak_bytes,
+    }
+}
+
+// Benchmarks all three stages of `day`. The parts reuse one parsed input, so
+// their timings don't include parsing.
+pub fn bench_day(day: &Day, input: &PuzzleInput, runs: usize) -> Result<Vec<StageResult>, String> {
+    let mut parse_times = Vec::new();
+    let mut parse_peak = 0;
+    let mut solver: Option<Box<dyn Solver>> = None;
+    for _ in 0..runs {
+        // Drop the previous run's result first so it doesn't count as in use.
+        drop(solver.take());
+        let (parsed, elapsed, peak) = measure(|| (day.parse)(input));
+        solver = Some(parsed.map_err(|error| error.diagnostic())?);
+        parse_times.push(elapsed);
+        parse_peak = parse_peak.max(peak);
+    }
+    let solver = solver.expect("runs is at least 1");
+
+    let mut results = vec![summarize(day, Stage::Parse, input, parse_times, parse_peak)];
+    for (stage, part) in [(Stage::Part1, 1), (Stage::Part2, 2)] {
+        let mut times = Vec::new();
+        let mut stage_peak = 0;
+        for _ in 0..runs {
+            let (_, elapsed, peak) = measure(|| solver.part(part));
+            times.push(elapsed);
+            stage_peak = stage_peak.max(peak);
+        }
+        results.push(summarize(day, stage, input, times, stage_peak));
+    }
+
+    Ok(results)
+}
+
+// Human-friendly duration, e.g. "1.25ms".
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.2}us", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+// Human-friendly size, e.g. "3.1MiB".
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{}B", bytes)
+    } else {
+        format!("{:.1}{}", value, UNITS[unit])
+    }
+}
+
+pub fn format(results: &[StageResult], format: Format) -> String {
+    match format {
+        Format::Text => to_text(results),
+        Format::Json => to_json(results),
+        Format::Csv => to_csv(results),
+    }
+}
+
+fn to_text(results: &[StageResult]) -> String {
+    let mut text = String::from("day  stage        min     median        max       peak\n");
+    for result in results {
+        text.push_str(&format!("{:>3}  {:<5}  {:>9}  {:>9}  {:>9}  {:>9}\n",
+                               result.day,
+                               result.stage.name(),
+                               format_duration(result.min),
+                               format_duration(result.median),
+                               format_duration(result.max),
+                               format_bytes(result.peak_bytes)));
+    }
+    text
+}
+
+fn to_json(results: &[StageResult]) -> String {
+    let rows: Vec<String> = results.iter()
+                                   .map(|result| {
+                                       format!("  {{\"day\": {}, \"stage\": \"{}\", \"input_hash\": \"{:016x}\", \
+                                                \"runs\": {}, \"min_ns\": {}, \"median_ns\": {}, \
+                                                \"max_ns\": {}, \"peak_bytes\": {}}}",
+                                               result.day,
+                                               result.stage.name(),
+                                               result.input_hash,
+                                               result.runs,
+                                               result.min.as_nanos(),
+                                               result.median.as_nanos(),
+                                               result.max.as_nanos(),
+                                               result.peak_bytes)
+                                   })
+                                   .collect();
+    format!("[\n{}\n]\n", rows.join(",\n"))
+}
+
+fn to_csv(results: &[StageResult]) -> String {
+    let mut text = String::from("day,stage,input_hash,runs,min_ns,median_ns,max_ns,peak_bytes\n");
+    for result in results {
+        text.push_str(&format!("{},{},{:016x},{},{},{},{},{}\n",
+                               result.day,
+                               result.stage.name(),
+                               result.input_hash,
+                               result.runs,
+                               result.min.as_nanos(),
+                               result.median.as_nanos(),
+                               result.max.as_nanos(),
+                               result.peak_bytes));
+    }
+    text
+}
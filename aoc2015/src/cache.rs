@@ -0,0 +1,228 @@
+// On-disk cache of answers, so re-running a day on an input it has already
+// solved returns straight away.
+//
+// The file has one answer per line:
+//
+//     <day> <part> <input hash> <solver version> <elapsed ns> <answer>
+//
+// A cached answer is only used for the same input (by `PuzzleInput::hash`,
+// which covers parameters too) and the same `Solution::VERSION` of the day.
+// The cache lives in the build directory, so `cargo clean` clears it as well
+// as `aoc2015 cache clear`.
+//
+// The cache is only ever a shortcut: if it can't be read, the days are solved
+// as if it were empty.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::time::Duration;
+
+use crate::output::Answer;
+
+pub const DEFAULT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../target/answer_cache.txt");
+
+const HEADER: &str = "# Cached answers: <day> <part> <input hash> <solver version> <elapsed ns> <answer>\n";
+
+#[derive(Clone)]
+pub struct Cached {
+    version: u32,
+    pub answer: String,
+    // How long the part took when it was solved
+    pub elapsed: Duration,
+}
+
+pub struct Cache {
+    path: String,
+    entries: BTreeMap<(u32, u32, u64), Cached>,
+}
+
+// Reads one line of the cache file, or None if it's malformed.
+fn parse_line(line: &str) -> Option<((u32, u32, u64), Cached)> {
+    let mut fields = line.splitn(6, ' ');
+    let day = fields.next()?.parse().ok()?;
+    let part = fields.next()?.parse().ok()?;
+    let hash = u64::from_str_radix(fields.next()?, 16).ok()?;
+    let version = fields.next()?.parse().ok()?;
+    let elapsed = Duration::from_nanos(fields.next()?.parse().ok()?);
+    let answer = fields.next()?.to_string();
+    Some(((day, part, hash), Cached { version, answer, elapsed }))
+}
+
+impl Cache {
+    // Loads the cache at `path`. A missing or unreadable file is an empty
+    // cache, and malformed lines are skipped.
+    pub fn load(path: &str) -> Cache {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(ref error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(error) => {
+                log::warn!("ignoring answer cache {}: {}", path, error);
+                String::new()
+            }
+        };
+
+        let mut entries = BTreeMap::new();
+        for (line_idx, line) in text.lines().enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match parse_line(line) {
+                Some((key, cached)) => {
+                    entries.insert(key, cached);
+                }
+                None => log::warn!("{}:{}: skipping malformed cache line", path, line_idx + 1),
+            }
+        }
+
+        Cache { path: path.to_string(), entries }
+    }
+
+    pub fn get(&self, day: u32, part: u32, hash: u64, version: u32) -> Option<&Cached> {
+        self.entries.get(&(day, part, hash)).filter(|cached| cached.version == version)
+    }
+
+    // Replaces any answer cached for an older version.
+    pub fn insert(&mut self, day: u32, part: u32, hash: u64, version: u32, answer: &str, elapsed: Duration) {
+        self.entries.insert((day, part, hash), Cached { version, answer: answer.to_string(), elapsed });
+    }
+
+    // Adds the answers the solver worked out (not the ones that came from the
+    // cache) for an input with the given hash. Returns how many were added.
+    pub fn add_solved(&mut self, version: u32, hash: u64, answers: &[Answer]) -> usize {
+        let solved: Vec<&Answer> = answers.iter().filter(|answer| !answer.cached).collect();
+        for answer in &solved {
+            self.insert(answer.day, answer.part, hash, version, &answer.answer, answer.elapsed);
+        }
+        solved.len()
+    }
+
+    // Rewrites the whole file. Written to a temporary file first, so a run
+    // that's stopped part way through can't leave half a cache behind.
+    pub fn save(&self) -> Result<(), String> {
+        let mut text = String::from(HEADER);
+        for (&(day, part, hash), cached) in &self.entries {
+            text.push_str(&format!("{} {} {:016x} {} {} {}\n",
+                                   day,
+                                   part,
+                                   hash,
+                                   cached.version,
+                                   cached.elapsed.as_nanos(),
+                                   cached.answer));
+        }
+
+        let temp_path = format!("{}.tmp", self.path);
+        let write = || -> io::Result<()> {
+            if let Some(dir) = std::path::Path::new(&self.path).parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(&temp_path, text)?;
+            fs::rename(&temp_path, &self.path)
+        };
+        write().map_err(|error| format!("couldn't write the answer cache {}: {}", self.path, error))
+    }
+}
+
+// Deletes the cache at `path`. Returns how many answers it held.
+pub fn clear(path: &str) -> Result<usize, String> {
+    let count = Cache::load(path).entries.len();
+    match fs::remove_file(path) {
+        Ok(()) => Ok(count),
+        Err(ref error) if error.kind() == io::ErrorKind::NotFound => Ok(0),
+        Err(error) => Err(format!("couldn't delete {}: {}", path, error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A file in the temp directory, unique to this test run.
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("aoc2015-{}-{}", std::process::id(), name))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn answer(part: u32, answer: &str, cached: bool) -> Answer {
+        Answer {
+            day: 25,
+            part,
+            answer: answer.to_string(),
+            elapsed: Duration::from_nanos(1500),
+            input: String::from("input.txt"),
+            cached,
+        }
+    }
+
+    #[test]
+    fn malformed_lines() {
+        let lines = ["", "7 1", "seven 1 00000000000000ff 1 1500 956", "7 1 not-hex 1 1500 956",
+                     "7 1 00000000000000ff v1 1500 956", "7 1 00000000000000ff 1 soon 956",
+                     "7 1 00000000000000ff 1 1500"];
+        for line in &lines {
+            assert!(parse_line(line).is_none(), "{:?}", line);
+        }
+
+        let ((day, part, hash), cached) = parse_line("25 2 00000000000000ff 3 1500 Merry Christmas!").unwrap();
+        assert_eq!((day, part, hash, cached.version), (25, 2, 0xff, 3));
+        assert_eq!((cached.answer.as_str(), cached.elapsed), ("Merry Christmas!", Duration::from_nanos(1500)));
+    }
+
+    #[test]
+    fn answers_are_kept_per_version() {
+        let mut cache = Cache::load(&temp_path("never-written"));
+        cache.insert(15, 2, 0xabc, 1, "11171160", Duration::from_millis(3));
+        assert_eq!(cache.get(15, 2, 0xabc, 1).map(|cached| cached.answer.as_str()), Some("11171160"));
+        assert!(cache.get(15, 2, 0xabc, 2).is_none());
+        assert!(cache.get(15, 2, 0xabd, 1).is_none());
+
+        // A newer solver's answer replaces the old one.
+        cache.insert(15, 2, 0xabc, 2, "8520000", Duration::from_millis(3));
+        assert!(cache.get(15, 2, 0xabc, 1).is_none());
+        assert_eq!(cache.get(15, 2, 0xabc, 2).map(|cached| cached.answer.as_str()), Some("8520000"));
+    }
+
+    #[test]
+    fn only_solved_answers_are_added() {
+        let mut cache = Cache::load(&temp_path("never-written-either"));
+        assert_eq!(cache.add_solved(1, 7, &[answer(1, "8997277", false), answer(2, "old", true)]), 1);
+        assert!(cache.get(25, 1, 7, 1).is_some());
+        assert!(cache.get(25, 2, 7, 1).is_none());
+    }
+
+    #[test]
+    fn save_and_load() {
+        let path = temp_path("cache-round-trip.txt");
+        let mut cache = Cache::load(&path);
+        cache.insert(25, 2, u64::MAX, 4, "Merry Christmas!", Duration::from_nanos(42));
+        cache.insert(1, 1, 0, 1, "74", Duration::from_secs(2));
+        cache.save().unwrap();
+
+        // Malformed lines are skipped rather than losing the rest.
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.starts_with(HEADER));
+        fs::write(&path, format!("{}garbage\n", text)).unwrap();
+
+        let loaded = Cache::load(&path);
+        let christmas = loaded.get(25, 2, u64::MAX, 4).unwrap();
+        assert_eq!((christmas.answer.as_str(), christmas.elapsed), ("Merry Christmas!", Duration::from_nanos(42)));
+        assert_eq!(loaded.get(1, 1, 0, 1).map(|cached| cached.elapsed), Some(Duration::from_secs(2)));
+        assert_eq!(loaded.entries.len(), 2);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn clearing() {
+        let path = temp_path("cache-clear.txt");
+        let mut cache = Cache::load(&path);
+        cache.insert(4, 1, 1, 1, "346386", Duration::ZERO);
+        cache.insert(4, 2, 1, 1, "9958218", Duration::ZERO);
+        cache.save().unwrap();
+
+        assert_eq!(clear(&path), Ok(2));
+        assert!(!std::path::Path::new(&path).exists());
+        assert_eq!(clear(&path), Ok(0));
+    }
+}
@@ -0,0 +1,254 @@
+// Table of every day linked into the runner.
+
+use std::time::Instant;
+
+use aoc_common::{ParseError, PuzzleInput, Solution, Solver};
+
+use crate::cache::Cache;
+use crate::output::Answer;
+
+pub struct Day {
+    pub number: u32,
+    // Bundled input file.
+    pub input: &'static str,
+    pub parse: fn(&PuzzleInput) -> Result<Box<dyn Solver>, ParseError>,
+    // `Solution::VERSION` of the day's solver.
+    pub version: u32,
+    // Random input from a seed and an optional size.
+    pub generate: fn(u64, Option<usize>) -> String,
+}
+
+impl Day {
+    // Loads `path` (or stdin for "-"), falling back to the bundled input, and
+    // attaches any KEY=VALUE parameters.
+    pub fn load_input(&self, path: Option<&str>, params: &[String]) -> Result<PuzzleInput, String> {
+        let input = match path {
+            Some("-") => PuzzleInput::from_stdin(),
+            Some(path) => PuzzleInput::from_path(path),
+            None => PuzzleInput::from_path(self.input),
+        };
+        let mut input = input.map_err(|error| error.to_string())?;
+
+        for param in params {
+            let (key, value) = param.split_once('=')
+                                    .ok_or_else(|| format!("expected KEY=VALUE, got '{}'", param))?;
+            input = input.with_param(key, value);
+        }
+        Ok(input)
+    }
+
+    // Parses `input` once and solves each of `parts` with it, taking answers
+    // from `cache` where it has them. The input is only parsed if some part
+    // isn't cached.
+    pub fn solve(&self, input: &PuzzleInput, parts: &[u32], cache: Option<&Cache>) -> Result<Vec<Answer>, String> {
+        let hash = input.hash();
+        let mut solver = None;
+        parts.iter()
+             .map(|&part| {
+                 let answer = |answer, elapsed, cached| Answer {
+                     day: self.number,
+                     part,
+                     answer,
+                     elapsed,
+                     input: input.name().to_string(),
+                     cached,
+                 };
+                 if let Some(cached) = cache.and_then(|cache| cache.get(self.number, part, hash, self.version)) {
+                     return Ok(answer(cached.answer.clone(), cached.elapsed, true));
+                 }
+
+                 if solver.is_none() {
+                     solver = Some((self.parse)(input).map_err(|error| error.diagnostic())?);
+                 }
+                 let solver = solver.as_ref().expect("parsed above");
+
+                 let start = Instant::now();
+                 let solved = solver.part(part)
+                                    .ok_or_else(|| format!("day {} has no part {}", self.number, part))?;
+                 Ok(answer(solved, start.elapsed(), false))
+             })
+             .collect()
+    }
+}
+
+pub fn find(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+pub static DAYS: [Day; 25] = [
+    Day {
+        number: 1,
+        input: day_1::INPUT,
+        parse: aoc_common::parse::<day_1::Day1>,
+        version: day_1::Day1::VERSION,
+        generate: aoc_common::generate::<day_1::Day1>,
+    },
+    Day {
+        number: 2,
+        input: day_2::INPUT,
+        parse: aoc_common::parse::<day_2::Day2>,
+        version: day_2::Day2::VERSION,
+        generate: aoc_common::generate::<day_2::Day2>,
+    },
+    Day {
+        number: 3,
+        input: day_3::INPUT,
+        parse: aoc_common::parse::<day_3::Day3>,
+        version: day_3::Day3::VERSION,
+        generate: aoc_common::generate::<day_3::Day3>,
+    },
+    Day {
+        number: 4,
+        input: day_4::INPUT,
+        parse: aoc_common::parse::<day_4::Day4>,
+        version: day_4::Day4::VERSION,
+        generate: aoc_common::generate::<day_4::Day4>,
+    },
+    Day {
+        number: 5,
+        input: day_5::INPUT,
+        parse: aoc_common::parse::<day_5::Day5>,
+        version: day_5::Day5::VERSION,
+        generate: aoc_common::generate::<day_5::Day5>,
+    },
+    Day {
+        number: 6,
+        input: day_6::INPUT,
+        parse: aoc_common::parse::<day_6::Day6>,
+        version: day_6::Day6::VERSION,
+        generate: aoc_common::generate::<day_6::Day6>,
+    },
+    Day {
+        number: 7,
+        input: day_7::INPUT,
+        parse: aoc_common::parse::<day_7::Day7>,
+        version: day_7::Day7::VERSION,
+        generate: aoc_common::generate::<day_7::Day7>,
+    },
+    Day {
+        number: 8,
+        input: day_8::INPUT,
+        parse: aoc_common::parse::<day_8::Day8>,
+        version: day_8::Day8::VERSION,
+        generate: aoc_common::generate::<day_8::Day8>,
+    },
+    Day {
+        number: 9,
+        input: day_9::INPUT,
+        parse: aoc_common::parse::<day_9::Day9>,
+        version: day_9::Day9::VERSION,
+        generate: aoc_common::generate::<day_9::Day9>,
+    },
+    Day {
+        number: 10,
+        input: day_10::INPUT,
+        parse: aoc_common::parse::<day_10::Day10>,
+        version: day_10::Day10::VERSION,
+        generate: aoc_common::generate::<day_10::Day10>,
+    },
+    Day {
+        number: 11,
+        input: day_11::INPUT,
+        parse: aoc_common::parse::<day_11::Day11>,
+        version: day_11::Day11::VERSION,
+        generate: aoc_common::generate::<day_11::Day11>,
+    },
+    Day {
+        number: 12,
+        input: day_12::INPUT,
+        parse: aoc_common::parse::<day_12::Day12>,
+        version: day_12::Day12::VERSION,
+        generate: aoc_common::generate::<day_12::Day12>,
+    },
+    Day {
+        number: 13,
+        input: day_13::INPUT,
+        parse: aoc_common::parse::<day_13::Day13>,
+        version: day_13::Day13::VERSION,
+        generate: aoc_common::generate::<day_13::Day13>,
+    },
+    Day {
+        number: 14,
+        input: day_14::INPUT,
+        parse: aoc_common::parse::<day_14::Day14>,
+        version: day_14::Day14::VERSION,
+        generate: aoc_common::generate::<day_14::Day14>,
+    },
+    Day {
+        number: 15,
+        input: day_15::INPUT,
+        parse: aoc_common::parse::<day_15::Day15>,
+        version: day_15::Day15::VERSION,
+        generate: aoc_common::generate::<day_15::Day15>,
+    },
+    Day {
+        number: 16,
+        input: day_16::INPUT,
+        parse: aoc_common::parse::<day_16::Day16>,
+        version: day_16::Day16::VERSION,
+        generate: aoc_common::generate::<day_16::Day16>,
+    },
+    Day {
+        number: 17,
+        input: day_17::INPUT,
+        parse: aoc_common::parse::<day_17::Day17>,
+        version: day_17::Day17::VERSION,
+        generate: aoc_common::generate::<day_17::Day17>,
+    },
+    Day {
+        number: 18,
+        input: day_18::INPUT,
+        parse: aoc_common::parse::<day_18::Day18>,
+        version: day_18::Day18::VERSION,
+        generate: aoc_common::generate::<day_18::Day18>,
+    },
+    Day {
+        number: 19,
+        input: day_19::INPUT,
+        parse: aoc_common::parse::<day_19::Day19>,
+        version: day_19::Day19::VERSION,
+        generate: aoc_common::generate::<day_19::Day19>,
+    },
+    Day {
+        number: 20,
+        input: day_20::INPUT,
+        parse: aoc_common::parse::<day_20::Day20>,
+        version: day_20::Day20::VERSION,
+        generate: aoc_common::generate::<day_20::Day20>,
+    },
+    Day {
+        number: 21,
+        input: day_21::INPUT,
+        parse: aoc_common::parse::<day_21::Day21>,
+        version: day_21::Day21::VERSION,
+        generate: aoc_common::generate::<day_21::Day21>,
+    },
+    Day {
+        number: 22,
+        input: day_22::INPUT,
+        parse: aoc_common::parse::<day_22::Day22>,
+        version: day_22::Day22::VERSION,
+        generate: aoc_common::generate::<day_22::Day22>,
+    },
+    Day {
+        number: 23,
+        input: day_23::INPUT,
+        parse: aoc_common::parse::<day_23::Day23>,
+        version: day_23::Day23::VERSION,
+        generate: aoc_common::generate::<day_23::Day23>,
+    },
+    Day {
+        number: 24,
+        input: day_24::INPUT,
+        parse: aoc_common::parse::<day_24::Day24>,
+        version: day_24::Day24::VERSION,
+        generate: aoc_common::generate::<day_24::Day24>,
+    },
+    Day {
+        number: 25,
+        input: day_25::INPUT,
+        parse: aoc_common::parse::<day_25::Day25>,
+        version: day_25::Day25::VERSION,
+        generate: aoc_common::generate::<day_25::Day25>,
+    },
+];
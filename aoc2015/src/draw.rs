@@ -0,0 +1,126 @@
+// Draws the days whose puzzles happen on a grid, as images in a directory:
+//
+//   day 3   heatmaps of presents per house: santa.ppm and robo_santa.ppm
+//   day 6   the light show after each instruction: lights_NNNN.pgm for part 1
+//           and brightness_NNNN.pgm for part 2
+//   day 18  each generation of the Life board: life_NNNN.pgm for part 1 and
+//           stuck_NNNN.pgm for part 2
+//
+// Animations keep every `every`th frame plus the last one. Frames can be
+// turned into a video with e.g. `ffmpeg -i lights_%04d.pgm lights.mp4`.
+
+use std::fs;
+use std::path::Path;
+
+use aoc_common::image::{self, Frames};
+use aoc_common::{Grid, PuzzleInput, Solution};
+
+use day_18::Day18;
+use day_3::Day3;
+use day_6::Day6;
+
+// Frames of one animation, skipping all but every `every`th one.
+struct Animation {
+    frames: Frames,
+    every: usize,
+    seen: usize,
+    // First error, since the solvers' frame callbacks can't return one
+    error: Option<String>,
+}
+
+impl Animation {
+    fn new(dir: &Path, prefix: &str, every: usize) -> Result<Animation, String> {
+        let frames = Frames::new(dir, prefix, "pgm")
+            .map_err(|error| format!("couldn't create {}: {}", dir.display(), error))?;
+        Ok(Animation { frames, every, seen: 0, error: None })
+    }
+
+    fn frame<T>(&mut self, grid: &Grid<T>, last: bool, shade: impl Fn(&T) -> u8) {
+        let idx = self.seen;
+        self.seen += 1;
+        if self.error.is_some() || (!idx.is_multiple_of(self.every) && !last) {
+            return;
+        }
+        if let Err(error) = self.frames.write(&image::pgm(grid, shade)) {
+            self.error = Some(format!("couldn't write a frame: {}", error));
+        }
+    }
+
+    // How many frames were written.
+    fn finish(self) -> Result<usize, String> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.frames.count()),
+        }
+    }
+}
+
+fn on_off(&on: &bool) -> u8 {
+    if on { 255 } else { 0 }
+}
+
+// Presents per house, brightest where the most were delivered.
+fn draw_day_3(input: &PuzzleInput, dir: &Path) -> Result<usize, String> {
+    let moves = Day3::parse(input).map_err(|error| error.diagnostic())?;
+    fs::create_dir_all(dir).map_err(|error| format!("couldn't create {}: {}", dir.display(), error))?;
+
+    for (name, dispatch) in [("santa.ppm", day_3::SANTA), ("robo_santa.ppm", day_3::SANTA_AND_ROBOT)] {
+        let path = dir.join(name);
+        fs::write(&path, day_3::report::heatmap(&day_3::deliver(&moves, &dispatch)))
+            .map_err(|error| format!("couldn't write {}: {}", path.display(), error))?;
+    }
+    Ok(2)
+}
+
+fn draw_day_6(input: &PuzzleInput, dir: &Path, every: usize) -> Result<usize, String> {
+    let commands = Day6::parse(input).map_err(|error| error.diagnostic())?;
+    let last = commands.len();
+
+    let mut lights = Animation::new(dir, "lights", every)?;
+    let mut idx = 0;
+    day_6::switch_lights(&commands, |grid| {
+        idx += 1;
+        lights.frame(grid, idx == last, on_off);
+    });
+
+    // Shade every frame against the brightest any light ever gets, so the
+    // frames can be compared with each other.
+    let mut brightest = 0;
+    day_6::dim_lights(&commands, |grid| brightest = brightest.max(grid.iter().cloned().max().unwrap_or(0)));
+
+    let mut brightness = Animation::new(dir, "brightness", every)?;
+    let mut idx = 0;
+    day_6::dim_lights(&commands, |grid| {
+        idx += 1;
+        brightness.frame(grid, idx == last, |&level| image::scale(level as u64, brightest as u64));
+    });
+
+    Ok(lights.finish()? + brightness.finish()?)
+}
+
+fn draw_day_18(input: &PuzzleInput, dir: &Path, every: usize) -> Result<usize, String> {
+    let initial = Day18::parse(input).map_err(|error| error.diagnostic())?;
+    let steps = 100;
+
+    let mut written = 0;
+    for &(prefix, stuck_corners) in &[("life", false), ("stuck", true)] {
+        let mut life = Animation::new(dir, prefix, every)?;
+        let mut generation = 0;
+        day_18::animate(&initial, stuck_corners, steps, |grid| {
+            life.frame(grid, generation == steps, on_off);
+            generation += 1;
+        });
+        written += life.finish()?;
+    }
+    Ok(written)
+}
+
+// Draws `day` into `dir` and returns how many images were written.
+pub fn draw(day: u32, input: &PuzzleInput, dir: &Path, every: usize) -> Result<usize, String> {
+    match day {
+        3 => draw_day_3(input, dir),
+        6 => draw_day_6(input, dir, every),
+        18 => draw_day_18(input, dir, every),
+        _ => Err(format!("day {} isn't a grid puzzle; only days 3, 6 and 18 can be drawn", day)),
+    }
+}
@@ -0,0 +1,65 @@
+// Logger for the day crates' `log` output, written to stderr so it never mixes
+// with answers on stdout.
+//
+// Each day logs under its crate name (day_1 .. day_25). Everything below
+// warnings is hidden unless asked for: `-v` shows debug messages from every
+// day, `-vv` shows trace messages too, and `--trace day22` shows trace
+// messages from just that day.
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+struct Logger {
+    default: LevelFilter,
+    // Per-target overrides, e.g. ("day_22", Trace)
+    targets: Vec<(String, LevelFilter)>,
+}
+
+impl Logger {
+    fn filter(&self, target: &str) -> LevelFilter {
+        self.targets
+            .iter()
+            .filter(|&(name, _)| target == name || target.starts_with(&format!("{}::", name)))
+            .map(|&(_, level)| level)
+            .fold(self.default, Ord::max)
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.filter(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{:<5} {}] {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+// Turns "day22", "day_22" or "22" into the crate name "day_22".
+fn day_target(name: &str) -> Result<String, String> {
+    let number = name.trim_start_matches("day").trim_start_matches('_');
+    match number.parse::<u32>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(format!("day_{}", day)),
+        _ => Err(format!("expected a day like day22 for --trace, got '{}'", name)),
+    }
+}
+
+pub fn init(verbosity: u8, trace_days: &[String]) -> Result<(), String> {
+    let default = match verbosity {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    };
+    let targets = trace_days.iter()
+                            .map(|name| Ok((day_target(name)?, LevelFilter::Trace)))
+                            .collect::<Result<Vec<_>, String>>()?;
+
+    let max_level = targets.iter().map(|&(_, level)| level).fold(default, Ord::max);
+    log::set_logger(Box::leak(Box::new(Logger { default, targets })))
+        .map_err(|error| error.to_string())?;
+    log::set_max_level(max_level);
+    Ok(())
+}
@@ -0,0 +1,432 @@
+// Runs any day of Advent of Code 2015 from a single binary.
+//
+//     aoc2015 run --day 7 --part 2 [--input path]
+//     aoc2015 run --day 1 --input - < floors.txt
+//     aoc2015 run --day 14 --input example.txt --param seconds=1000
+//     aoc2015 run --day 9 --format json
+//     aoc2015 run --all --jobs 4
+//     aoc2015 run --day 4 --no-cache
+//     aoc2015 cache clear
+//     aoc2015 verify [--day 18] [--record]
+//     aoc2015 bench [--day 4] [--runs 10] [--format json] [--output bench.json]
+//     aoc2015 gen --day 7 --seed 3 [--size 500] | aoc2015 run --day 7 --input -
+//     aoc2015 draw --day 18 --output frames/ [--every 5]
+//     aoc2015 watch --day 23 --input day_23/day23_test.txt [--params-file params.txt]
+//     aoc2015 -v run --day 20
+//     aoc2015 run --day 22 --trace day22
+
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+
+use clap::{ArgAction, Parser, Subcommand};
+
+use answers::Answers;
+use bench::Format;
+use cache::Cache;
+use run_all::panic_message;
+
+mod alloc;
+mod answers;
+mod bench;
+mod cache;
+mod days;
+mod draw;
+mod logger;
+mod output;
+mod run_all;
+mod watch;
+
+#[global_allocator]
+static ALLOCATOR: alloc::CountingAllocator = alloc::CountingAllocator;
+
+#[derive(Parser)]
+#[command(name = "aoc2015", about = "Advent of Code 2015 solutions")]
+struct Cli {
+    /// Show debug output from the solvers (-vv for trace output)
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// Show trace output from one day, e.g. --trace day22
+    #[arg(long = "trace", value_name = "DAY", global = true)]
+    trace: Vec<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one or both parts of a single day, or of every day with --all
+    Run {
+        /// Day to run (1-25)
+        #[arg(long, required_unless_present = "all")]
+        day: Option<u32>,
+
+        /// Run every day, several at once
+        #[arg(long, conflicts_with_all = ["day", "input", "params"])]
+        all: bool,
+
+        /// How many days to run at once with --all. Defaults to one per CPU.
+        #[arg(long, requires = "all", value_parser = clap::value_parser!(u32).range(1..))]
+        jobs: Option<u32>,
+
+        /// Part to run (1 or 2). Runs both parts if omitted.
+        #[arg(long)]
+        part: Option<u32>,
+
+        /// Input file to use instead of the day's bundled input, or - for stdin
+        #[arg(long)]
+        input: Option<String>,
+
+        /// Puzzle setting that isn't part of the input, e.g. seconds=1000 for day 14
+        #[arg(long = "param", value_name = "KEY=VALUE")]
+        params: Vec<String>,
+
+        /// Output format: `day N part P: answer` lines, or one JSON object per part
+        #[arg(long, value_enum, default_value_t = output::Format::Text)]
+        format: output::Format,
+
+        /// Solve every part even if its answer is cached, and don't cache the answers
+        #[arg(long)]
+        no_cache: bool,
+    },
+    /// Manage the cache of answers kept by the run command
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+    /// Check answers against the registry of known answers
+    Verify {
+        /// Only verify this day. Verifies every day if omitted.
+        #[arg(long)]
+        day: Option<u32>,
+
+        /// Input file to verify instead of the day's bundled input (needs --day)
+        #[arg(long, requires = "day")]
+        input: Option<String>,
+
+        /// Puzzle setting that isn't part of the input, e.g. seconds=1000 for day 14
+        #[arg(long = "param", value_name = "KEY=VALUE", requires = "day")]
+        params: Vec<String>,
+
+        /// Answers file to check against
+        #[arg(long, default_value = answers::DEFAULT_PATH)]
+        answers: String,
+
+        /// Add answers that aren't in the registry yet
+        #[arg(long)]
+        record: bool,
+    },
+    /// Time the parse, part 1 and part 2 stages of each day
+    Bench {
+        /// Only benchmark this day. Benchmarks every day if omitted.
+        #[arg(long)]
+        day: Option<u32>,
+
+        /// Input file to benchmark instead of the day's bundled input (needs --day)
+        #[arg(long, requires = "day")]
+        input: Option<String>,
+
+        /// Puzzle setting that isn't part of the input, e.g. seconds=1000 for day 14
+        #[arg(long = "param", value_name = "KEY=VALUE", requires = "day")]
+        params: Vec<String>,
+
+        /// How many times to run each stage
+        #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..))]
+        runs: u32,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+
+        /// Write the results to this file instead of stdout
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Write a random input for a day, the same one every time for a given seed
+    Gen {
+        /// Day to generate an input for (1-25)
+        #[arg(long)]
+        day: u32,
+
+        /// Seed for the random input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// How big to make the input. What it counts depends on the day.
+        #[arg(long)]
+        size: Option<usize>,
+
+        /// Write the input to this file instead of stdout
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Draw a grid puzzle (day 3, 6 or 18) as images, with a frame per step
+    Draw {
+        /// Day to draw
+        #[arg(long)]
+        day: u32,
+
+        /// Input file to draw instead of the day's bundled input, or - for stdin
+        #[arg(long)]
+        input: Option<String>,
+
+        /// Directory to write the images to
+        #[arg(long)]
+        output: String,
+
+        /// Only keep every Nth frame of an animation (the last one is always kept)
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        every: u32,
+    },
+    /// Re-run both parts of a day every time its input changes
+    Watch {
+        /// Day to run (1-25)
+        #[arg(long)]
+        day: u32,
+
+        /// Input file to watch instead of the day's bundled input
+        #[arg(long)]
+        input: Option<String>,
+
+        /// Puzzle setting that isn't part of the input, e.g. seconds=1000 for day 14
+        #[arg(long = "param", value_name = "KEY=VALUE")]
+        params: Vec<String>,
+
+        /// File of KEY=VALUE puzzle settings, one per line, to watch as well
+        #[arg(long)]
+        params_file: Option<String>,
+
+        /// How often to check for changes, in milliseconds
+        #[arg(long, default_value_t = 500, value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheCommand {
+    /// Delete every cached answer
+    Clear,
+}
+
+fn run(day_number: u32,
+       part: Option<u32>,
+       input: Option<String>,
+       params: Vec<String>,
+       format: output::Format,
+       use_cache: bool)
+       -> Result<(), String> {
+    let day = days::find(day_number).ok_or_else(|| format!("there is no day {}", day_number))?;
+
+    let input = day.load_input(input.as_deref(), &params)?;
+
+    let parts = part.map_or(vec![1, 2], |part| vec![part]);
+
+    let mut cache = if use_cache { Some(Cache::load(cache::DEFAULT_PATH)) } else { None };
+    let answers = day.solve(&input, &parts, cache.as_ref())?;
+    for answer in &answers {
+        println!("{}", output::format(answer, format));
+    }
+
+    if let Some(ref mut cache) = cache {
+        if cache.add_solved(day.version, input.hash(), &answers) > 0 {
+            // The answers are already out, so a cache that can't be written
+            // shouldn't fail the run.
+            if let Err(message) = cache.save() {
+                log::warn!("{}", message);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn clear_cache() -> Result<(), String> {
+    let cleared = cache::clear(cache::DEFAULT_PATH)?;
+    println!("Cleared {} cached answers", cleared);
+    Ok(())
+}
+
+fn verify(day_number: Option<u32>,
+          input: Option<String>,
+          params: Vec<String>,
+          answers_path: &str,
+          record: bool)
+          -> Result<(), String> {
+    let mut answers = Answers::load(answers_path)?;
+
+    let days = match day_number {
+        Some(number) => vec![days::find(number).ok_or_else(|| format!("there is no day {}", number))?],
+        None => days::DAYS.iter().collect(),
+    };
+
+    println!("day  part  status   answer");
+
+    // A day that fails to load, parse or solve gets an error row, and the
+    // rest are still checked.
+    let mut failures = 0;
+    let mut errors = 0;
+    let mut recorded = 0;
+    let mut error_row = |day: u32, part: &str, message: &str| {
+        errors += 1;
+        eprintln!("error: day {}: {}", day, message);
+        println!("{:>3}  {:>4}  {:<7}  {}", day, part, "ERROR", message.lines().next().unwrap_or(""));
+    };
+    for day in days {
+        let loaded = panic::catch_unwind(AssertUnwindSafe(|| {
+                         let input = day.load_input(input.as_deref(), &params)?;
+                         let solver = (day.parse)(&input).map_err(|error| error.diagnostic())?;
+                         Ok((input.hash(), solver))
+                     }))
+                     .unwrap_or_else(|payload| Err(panic_message(payload)));
+        let (hash, solver) = match loaded {
+            Ok(loaded) => loaded,
+            Err(message) => {
+                error_row(day.number, "-", &message);
+                continue;
+            }
+        };
+
+        for part in 1..=2 {
+            let answer = match panic::catch_unwind(AssertUnwindSafe(|| solver.part(part).expect("every day has two parts"))) {
+                Ok(answer) => answer,
+                Err(payload) => {
+                    error_row(day.number, &part.to_string(), &panic_message(payload));
+                    continue;
+                }
+            };
+            let (status, note) = match answers.get(day.number, part, hash) {
+                Some(expected) if expected == answer => ("pass", String::new()),
+                Some(expected) => {
+                    failures += 1;
+                    ("FAIL", format!(" (expected {})", expected))
+                }
+                None => {
+                    if record {
+                        answers.insert(day.number, part, hash, &answer);
+                        recorded += 1;
+                    }
+                    ("unknown", String::new())
+                }
+            };
+            println!("{:>3}  {:>4}  {:<7}  {}{}", day.number, part, status, answer, note);
+        }
+    }
+
+    if recorded > 0 {
+        answers.save()?;
+        println!("Recorded {} new answers in {}", recorded, answers_path);
+    }
+
+    match (failures, errors) {
+        (0, 0) => Ok(()),
+        (failures, 0) => Err(format!("{} of the answers did not match", failures)),
+        (0, errors) => Err(format!("{} of the days or parts failed", errors)),
+        (failures, errors) => Err(format!("{} of the answers did not match and {} of the days or parts failed", failures, errors)),
+    }
+}
+
+fn bench(day_number: Option<u32>,
+         input: Option<String>,
+         params: Vec<String>,
+         runs: u32,
+         format: Format,
+         output: Option<String>)
+         -> Result<(), String> {
+    let days = match day_number {
+        Some(number) => vec![days::find(number).ok_or_else(|| format!("there is no day {}", number))?],
+        None => days::DAYS.iter().collect(),
+    };
+
+    let mut results = Vec::new();
+    for day in days {
+        let input = day.load_input(input.as_deref(), &params)?;
+        results.extend(bench::bench_day(day, &input, runs as usize)?);
+    }
+
+    let report = bench::format(&results, format);
+    match output {
+        Some(path) => fs::write(&path, report).map_err(|error| format!("couldn't write {}: {}", path, error)),
+        None => {
+            print!("{}", report);
+            Ok(())
+        }
+    }
+}
+
+fn generate(day_number: u32, seed: u64, size: Option<usize>, output: Option<String>) -> Result<(), String> {
+    let day = days::find(day_number).ok_or_else(|| format!("there is no day {}", day_number))?;
+
+    let mut text = (day.generate)(seed, size);
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+
+    match output {
+        Some(path) => fs::write(&path, text).map_err(|error| format!("couldn't write {}: {}", path, error)),
+        None => {
+            print!("{}", text);
+            Ok(())
+        }
+    }
+}
+
+fn draw(day_number: u32, input: Option<String>, output: &str, every: u32) -> Result<(), String> {
+    let day = days::find(day_number).ok_or_else(|| format!("there is no day {}", day_number))?;
+    let input = day.load_input(input.as_deref(), &[])?;
+
+    let written = draw::draw(day.number, &input, Path::new(output), every as usize)?;
+    println!("Wrote {} images to {}", written, output);
+    Ok(())
+}
+
+fn watch(day_number: u32,
+         input: Option<String>,
+         params: Vec<String>,
+         params_file: Option<String>,
+         interval: u64)
+         -> Result<(), String> {
+    let day = days::find(day_number).ok_or_else(|| format!("there is no day {}", day_number))?;
+    let input = input.unwrap_or_else(|| day.input.to_string());
+    if input == "-" {
+        return Err(String::from("can't watch stdin; give --input a file"));
+    }
+
+    watch::watch(day, &input, &params, params_file.as_deref(), Duration::from_millis(interval))
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    if let Err(message) = logger::init(cli.verbose, &cli.trace) {
+        eprintln!("error: {}", message);
+        process::exit(1);
+    }
+
+    let result = match cli.command {
+        Command::Run { all: true, part, jobs, format, no_cache, .. } => run_all::run_all(part, jobs, format, !no_cache),
+        Command::Run { day, part, input, params, format, no_cache, .. } => {
+            run(day.expect("clap requires --day without --all"), part, input, params, format, !no_cache)
+        }
+        Command::Cache { command: CacheCommand::Clear } => clear_cache(),
+        Command::Verify { day, input, params, answers, record } => {
+            verify(day, input, params, &answers, record)
+        }
+        Command::Bench { day, input, params, runs, format, output } => {
+            bench(day, input, params, runs, format, output)
+        }
+        Command::Gen { day, seed, size, output } => generate(day, seed, size, output),
+        Command::Draw { day, input, output, every } => draw(day, input, &output, every),
+        Command::Watch { day, input, params, params_file, interval } => {
+            watch(day, input, params, params_file, interval)
+        }
+    };
+
+    if let Err(message) = result {
+        eprintln!("error: {}", message);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,93 @@
+// Formats the answers printed by the run command.
+//
+// Text output is one `day N part P: answer` line per part. JSON output is one
+// object per line (JSON Lines), so scripts can read answers without scraping:
+//
+//     {"day": 7, "part": 1, "answer": "956", "elapsed_ns": 1520, "input": "/src/aoc/day_7/day7.txt", "cached": false}
+//
+// Answers are always JSON strings, since some days answer with text. Cached
+// answers report how long they took when they were solved.
+
+use std::time::Duration;
+
+use clap::ValueEnum;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+pub struct Answer {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    // Time taken by the part, not counting parsing.
+    pub elapsed: Duration,
+    // Name of the input the answer was computed from.
+    pub input: String,
+    // Whether the answer came from the answer cache instead of the solver.
+    pub cached: bool,
+}
+
+pub fn format(answer: &Answer, format: Format) -> String {
+    match format {
+        Format::Text => format!("day {} part {}: {}", answer.day, answer.part, answer.answer),
+        Format::Json => {
+            format!("{{\"day\": {}, \"part\": {}, \"answer\": {}, \"elapsed_ns\": {}, \"input\": {}, \"cached\": {}}}",
+                    answer.day,
+                    answer.part,
+                    json_string(&answer.answer),
+                    answer.elapsed.as_nanos(),
+                    json_string(&answer.input),
+                    answer.cached)
+        }
+    }
+}
+
+// Quotes `text` as a JSON string.
+fn json_string(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_escapes() {
+        assert_eq!(json_string("956"), "\"956\"");
+        assert_eq!(json_string("say \"hi\" \\ bye"), "\"say \\\"hi\\\" \\\\ bye\"");
+        assert_eq!(json_string("a\nb\r\tc"), "\"a\\nb\\r\\tc\"");
+        assert_eq!(json_string("\u{0}\u{1b}\u{7f}é"), "\"\\u0000\\u001b\u{7f}é\"");
+    }
+
+    #[test]
+    fn json_lines() {
+        let answer = Answer {
+            day: 25,
+            part: 2,
+            answer: String::from("Merry \"Christmas\"!"),
+            elapsed: Duration::from_nanos(1520),
+            input: String::from("C:\\aoc\\input.txt"),
+            cached: true,
+        };
+        assert_eq!(format(&answer, Format::Json),
+                   "{\"day\": 25, \"part\": 2, \"answer\": \"Merry \\\"Christmas\\\"!\", \"elapsed_ns\": 1520, \
+                    \"input\": \"C:\\\\aoc\\\\input.txt\", \"cached\": true}");
+        assert_eq!(format(&answer, Format::Text), "day 25 part 2: Merry \"Christmas\"!");
+    }
+}
@@ -0,0 +1,144 @@
+// Runs every day at once on a small pool of worker threads.
+//
+// Days are independent, so each worker takes the next day that hasn't been
+// started yet until none are left. Answers are printed in day order once all
+// days are done. A day that panics or fails to load only fails that day; the
+// rest still run, and the summary lists what went wrong.
+//
+// Workers share one read-only answer cache. Newly solved answers are added to
+// it and saved once every day is done.
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::bench::format_duration;
+use crate::cache::Cache;
+use crate::days::{self, Day};
+use crate::output::{self, Answer, Format};
+
+struct DayResult {
+    day: u32,
+    answers: Result<Vec<Answer>, String>,
+    // Hash of the input, if it loaded
+    hash: Option<u64>,
+    elapsed: Duration,
+}
+
+// Turns a panic payload back into its message.
+pub fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        format!("panicked: {}", message)
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        format!("panicked: {}", message)
+    } else {
+        String::from("panicked")
+    }
+}
+
+fn run_day(day: &Day, parts: &[u32], cache: Option<&Cache>) -> DayResult {
+    let start = Instant::now();
+    let mut hash = None;
+    let answers = panic::catch_unwind(AssertUnwindSafe(|| {
+                      let input = day.load_input(None, &[])?;
+                      hash = Some(input.hash());
+                      day.solve(&input, parts, cache)
+                  }))
+                  .unwrap_or_else(|payload| Err(panic_message(payload)));
+
+    DayResult {
+        day: day.number,
+        answers,
+        hash,
+        elapsed: start.elapsed(),
+    }
+}
+
+// Runs `parts` (both if None) of every day on `jobs` threads, defaulting to
+// one per CPU.
+pub fn run_all(part: Option<u32>, jobs: Option<u32>, format: Format, use_cache: bool) -> Result<(), String> {
+    let parts = part.map_or(vec![1, 2], |part| vec![part]);
+    let jobs = match jobs {
+        Some(jobs) => jobs as usize,
+        None => thread::available_parallelism().map_or(1, |jobs| jobs.get()),
+    };
+    let jobs = jobs.min(days::DAYS.len());
+
+    let mut cache = if use_cache { Some(Cache::load(crate::cache::DEFAULT_PATH)) } else { None };
+
+    let start = Instant::now();
+    let next_day = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let next_day = &next_day;
+            let parts = &parts;
+            let cache = cache.as_ref();
+            scope.spawn(move || {
+                while let Some(day) = days::DAYS.get(next_day.fetch_add(1, Ordering::Relaxed)) {
+                    let result = run_day(day, parts, cache);
+                    sender.send(result).expect("results are collected after all workers finish");
+                }
+            });
+        }
+    });
+    drop(sender);
+    let elapsed = start.elapsed();
+
+    let mut results: Vec<DayResult> = receiver.into_iter().collect();
+    results.sort_by_key(|result| result.day);
+
+    let mut summary = String::from("day  status    elapsed\n");
+    let mut failures = 0;
+    let mut solved = 0;
+    for result in &results {
+        match result.answers {
+            Ok(ref answers) => {
+                for answer in answers {
+                    println!("{}", output::format(answer, format));
+                }
+                if let (Some(cache), Some(hash)) = (cache.as_mut(), result.hash) {
+                    let version = days::find(result.day).expect("results are for known days").version;
+                    solved += cache.add_solved(version, hash, answers);
+                }
+                let status = if answers.iter().all(|answer| answer.cached) { "cached" } else { "ok" };
+                summary.push_str(&format!("{:>3}  {:<6}  {:>9}\n", result.day, status, format_duration(result.elapsed)));
+            }
+            Err(ref message) => {
+                failures += 1;
+                eprintln!("error: day {}: {}", result.day, message);
+                summary.push_str(&format!("{:>3}  {:<6}  {:>9}  {}\n",
+                                          result.day,
+                                          "FAILED",
+                                          format_duration(result.elapsed),
+                                          message.lines().next().unwrap_or("")));
+            }
+        }
+    }
+    summary.push_str(&format!("{} days, {} failed, {} total on {} jobs\n",
+                              results.len(),
+                              failures,
+                              format_duration(elapsed),
+                              jobs));
+
+    if let Some(cache) = cache.filter(|_| solved > 0) {
+        if let Err(message) = cache.save() {
+            log::warn!("{}", message);
+        }
+    }
+
+    // Keep stdout as pure JSON Lines in JSON mode.
+    match format {
+        Format::Text => print!("\n{}", summary),
+        Format::Json => eprint!("{}", summary),
+    }
+
+    if failures > 0 {
+        return Err(format!("{} of the days failed", failures));
+    }
+    Ok(())
+}
@@ -0,0 +1,102 @@
+// Re-runs a day whenever its input changes, for trying out hand-made inputs.
+//
+// The input file, and the file of KEY=VALUE parameters if there is one, are
+// polled every `interval`. When either changes, the day is parsed and both
+// parts solved again, and each answer is printed next to the one from the
+// previous run:
+//
+//     run 3: day23_test.txt
+//     day 23 part 1: 2 (was 0)
+//     day 23 part 2: 7 (unchanged)
+//
+// Bad input doesn't stop the watch: the error is printed and the next change
+// is compared with the last run that worked. Answers are never cached.
+
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::bench::format_duration;
+use crate::days::Day;
+use crate::run_all::panic_message;
+
+// Reads a parameters file: one KEY=VALUE per line, skipping blank lines and
+// lines starting with '#'.
+fn read_params(path: &str) -> Result<Vec<String>, String> {
+    let text = fs::read_to_string(path).map_err(|error| format!("couldn't read {}: {}", path, error))?;
+    Ok(text.lines()
+           .map(str::trim)
+           .filter(|line| !line.is_empty() && !line.starts_with('#'))
+           .map(String::from)
+           .collect())
+}
+
+// Contents of each watched file, or None if it can't be read right now (e.g.
+// while an editor is replacing it).
+fn snapshot(paths: &[&str]) -> Vec<Option<Vec<u8>>> {
+    paths.iter().map(|path| fs::read(path).ok()).collect()
+}
+
+// Solves both parts of `day` and prints them against `previous`, which is
+// updated if the run works.
+fn run_once(day: &Day, input: &str, params: &[String], params_file: Option<&str>, previous: &mut Vec<String>) {
+    let start = Instant::now();
+    let answers = panic::catch_unwind(AssertUnwindSafe(|| {
+                      let mut params = params.to_vec();
+                      if let Some(path) = params_file {
+                          params.extend(read_params(path)?);
+                      }
+                      let input = day.load_input(Some(input), &params)?;
+                      day.solve(&input, &[1, 2], None)
+                  }))
+                  .unwrap_or_else(|payload| Err(panic_message(payload)));
+
+    let answers = match answers {
+        Ok(answers) => answers,
+        Err(message) => {
+            println!("error: {}", message);
+            return;
+        }
+    };
+
+    for (idx, answer) in answers.iter().enumerate() {
+        let change = match previous.get(idx) {
+            None => String::new(),
+            Some(before) if *before == answer.answer => String::from(" (unchanged)"),
+            Some(before) => format!(" (was {})", before),
+        };
+        println!("day {} part {}: {}{}", answer.day, answer.part, answer.answer, change);
+    }
+    println!("solved in {}", format_duration(start.elapsed()));
+    *previous = answers.into_iter().map(|answer| answer.answer).collect();
+}
+
+// Watches `input` (and `params_file`) until the process is stopped. Only fails
+// if the files can't be read to begin with.
+pub fn watch(day: &Day,
+             input: &str,
+             params: &[String],
+             params_file: Option<&str>,
+             interval: Duration)
+             -> Result<(), String> {
+    let paths: Vec<&str> = Some(input).into_iter().chain(params_file).collect();
+    for path in &paths {
+        fs::metadata(path).map_err(|error| format!("couldn't watch {}: {}", path, error))?;
+    }
+    println!("Watching {} for changes (Ctrl-C to stop)", paths.join(" and "));
+
+    let mut seen = None;
+    let mut previous = Vec::new();
+    let mut runs = 0;
+    loop {
+        let current = snapshot(&paths);
+        if seen.as_ref() != Some(&current) {
+            runs += 1;
+            println!("\nrun {}: {}", runs, input);
+            run_once(day, input, params, params_file, &mut previous);
+            seen = Some(current);
+        }
+        thread::sleep(interval);
+    }
+}
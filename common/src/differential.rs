@@ -0,0 +1,79 @@
+//! Differential testing: a day with a clever (fast) solver can register a slow,
+//! obviously correct reference solver next to it, and `check` runs both on
+//! generated inputs until they disagree.
+//!
+//! Inputs are tried from the smallest size up, so the first disagreement found
+//! is also the smallest input that shows it.
+
+use std::fmt;
+use std::ops::{Range, RangeInclusive};
+
+use crate::{Generator, PuzzleInput, Rng, Solution};
+
+/// A day with a reference solver to check its fast one against.
+pub trait Differential: Solution + Generator {
+    /// What the two solvers are compared on, e.g. both parts as a tuple.
+    type Answer: PartialEq + fmt::Debug;
+
+    /// Generator sizes small enough for the reference solver.
+    const SIZES: RangeInclusive<usize>;
+
+    /// The slow, obviously correct answer.
+    fn reference(input: &Self::Input) -> Self::Answer;
+    /// The answer the day actually gives.
+    fn fast(input: &Self::Input) -> Self::Answer;
+}
+
+/// The smallest generated input the two solvers disagree on.
+#[derive(Debug)]
+pub struct Mismatch {
+    pub seed: u64,
+    pub size: usize,
+    pub input: String,
+    pub reference: String,
+    pub fast: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "reference and fast solvers disagree on seed {} at size {}:", self.seed, self.size)?;
+        writeln!(f, "{}", self.input)?;
+        writeln!(f, "reference: {}", self.reference)?;
+        write!(f, "fast:      {}", self.fast)
+    }
+}
+
+/// Compares the solvers on every seed in `seeds` at every size in `D::SIZES`.
+/// At the first size with a disagreement, reports the shortest failing input.
+pub fn check<D: Differential>(seeds: Range<u64>) -> Result<(), Mismatch> {
+    for size in D::SIZES {
+        let shortest = seeds.clone()
+                            .filter_map(|seed| compare::<D>(seed, size))
+                            .min_by_key(|mismatch| mismatch.input.len());
+        if let Some(mismatch) = shortest {
+            return Err(mismatch);
+        }
+    }
+    Ok(())
+}
+
+fn compare<D: Differential>(seed: u64, size: usize) -> Option<Mismatch> {
+    let text = D::generate(&mut Rng::new(seed), size);
+    let input = PuzzleInput::from_text("generated", &text).expect("generated inputs are never empty");
+    let input = D::parse(&input).unwrap_or_else(|error| {
+        panic!("generated input for seed {} at size {} doesn't parse:\n{}", seed, size, error.diagnostic())
+    });
+
+    let reference = D::reference(&input);
+    let fast = D::fast(&input);
+    if reference == fast {
+        return None;
+    }
+    Some(Mismatch {
+        seed,
+        size,
+        input: text,
+        reference: format!("{:?}", reference),
+        fast: format!("{:?}", fast),
+    })
+}
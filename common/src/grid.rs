@@ -0,0 +1,244 @@
+//! 2D grids shared by the days that work on a plane of cells.
+//!
+//! `Grid` is dense: a fixed width and height with (0, 0) in the top left, for
+//! puzzles like day 6's lights or day 18's Life board. `SparseGrid` only
+//! stores the cells that have been set and grows in every direction, for
+//! puzzles like day 3 where the area isn't known up front. Either can be
+//! written out as an image with the `image` module.
+//!
+//! Rows grow downwards, like in an image, so "up" is y - 1.
+
+use std::collections::hash_map::{self, HashMap};
+use std::ops::{Index, IndexMut};
+
+use crate::parse::ParseError;
+use crate::PuzzleInput;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+/// A cell's position, in columns from the left and rows from the top.
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+// Offsets to the eight cells around a point.
+const NEIGHBOURS: [(i32, i32); 8] = [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+impl Point {
+    pub fn new(x: i32, y: i32) -> Point {
+        Point { x, y }
+    }
+
+    /// The point `dx` columns right and `dy` rows down from this one.
+    pub fn offset(self, dx: i32, dy: i32) -> Point {
+        Point::new(self.x + dx, self.y + dy)
+    }
+
+    /// The eight points around this one, with no bounds to check against.
+    pub fn neighbours(self) -> impl Iterator<Item = Point> {
+        NEIGHBOURS.iter().map(move |&(dx, dy)| self.offset(dx, dy))
+    }
+}
+
+/// A fixed-size rectangle of cells.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    // Row by row from the top
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// A `width` by `height` grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid { width, height, cells: vec![fill; width * height] }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Reads a block of characters, one row per line, turning each character
+    /// into a cell with `cell`. Every row must be as wide as the first.
+    /// `expected` describes the characters `cell` accepts, e.g. "'#' or '.'".
+    pub fn parse(input: &PuzzleInput, expected: &str, cell: impl Fn(char) -> Option<T>) -> Result<Grid<T>, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in input.tokens() {
+            let text = line.text();
+            let row_width = text.chars().count();
+            let width = *width.get_or_insert(row_width);
+            if row_width < width {
+                return Err(line.error_at_end(&format!("{} cells in a row", width)));
+            }
+            if row_width > width {
+                let offset = text.char_indices().nth(width).map_or(text.len(), |(offset, _)| offset);
+                return Err(line.error_at(offset, row_width - width, &format!("{} cells in a row", width),
+                                         &row_width.to_string()));
+            }
+
+            for (offset, c) in text.char_indices() {
+                match cell(c) {
+                    Some(value) => cells.push(value),
+                    None => return Err(line.error_at(offset, 1, expected, &format!("'{}'", c))),
+                }
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Grid { width, height, cells }),
+            _ => Err(input.last_line().error_at_end("a row of cells")),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `point` is on the grid.
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= 0 && point.y >= 0 && (point.x as usize) < self.width && (point.y as usize) < self.height
+    }
+
+    fn position(&self, point: Point) -> Option<usize> {
+        if self.contains(point) {
+            Some(point.y as usize * self.width + point.x as usize)
+        } else {
+            None
+        }
+    }
+
+    /// None outside the grid.
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.position(point).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.position(point).map(move |idx| &mut self.cells[idx])
+    }
+
+    /// The cells of row `y`, from the left.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Every point on the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.width * self.height).map(move |idx| Point::new((idx % width) as i32, (idx / width) as i32))
+    }
+
+    /// Every cell, row by row.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    /// The (up to eight) neighbours of `point` that are on the grid.
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        point.neighbours().filter(move |&neighbour| self.contains(neighbour))
+    }
+
+    /// A grid of the same size with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        match self.get(point) {
+            Some(cell) => cell,
+            None => panic!("{:?} is outside the {}x{} grid", point, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(point) {
+            Some(cell) => cell,
+            None => panic!("{:?} is outside the {}x{} grid", point, width, height),
+        }
+    }
+}
+
+/// Cells that have been set, anywhere on the plane.
+#[derive(Clone, Debug, Default)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid { cells: HashMap::new() }
+    }
+
+    /// How many cells have been set.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    /// Sets the cell at `point`, returning what was there before.
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.cells.insert(point, value)
+    }
+
+    pub fn entry(&mut self, point: Point) -> hash_map::Entry<'_, Point, T> {
+        self.cells.entry(point)
+    }
+
+    /// Set cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&point, value)| (point, value))
+    }
+
+    /// The top left and bottom right corners of the smallest rectangle holding
+    /// every set cell, or None if there aren't any.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        let mut points = self.cells.keys();
+        let first = *points.next()?;
+        Some(points.fold((first, first), |(min, max), point| {
+            (Point::new(min.x.min(point.x), min.y.min(point.y)), Point::new(max.x.max(point.x), max.y.max(point.y)))
+        }))
+    }
+
+    /// Copies the set cells onto a dense grid just big enough to hold them,
+    /// filling the gaps with `empty`. Also returns where the dense grid's
+    /// (0, 0) is on the sparse one.
+    pub fn to_dense(&self, empty: T) -> (Grid<T>, Point)
+        where T: Clone
+    {
+        let (min, max) = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return (Grid::new(0, 0, empty), Point::default()),
+        };
+
+        let width = (max.x - min.x + 1) as usize;
+        let height = (max.y - min.y + 1) as usize;
+        let mut grid = Grid::new(width, height, empty);
+        for (point, value) in self.iter() {
+            grid[point.offset(-min.x, -min.y)] = value.clone();
+        }
+        (grid, min)
+    }
+}
@@ -0,0 +1,77 @@
+//! Writes grids out as Netpbm images: PGM for greyscale and PPM for colour.
+//! Both are a short text header followed by one byte per channel, and most
+//! image viewers (and ffmpeg, for turning frames into a video) read them.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::grid::Grid;
+
+/// A greyscale image, with `shade` turning each cell into 0 (black) up to 255
+/// (white).
+pub fn pgm<T>(grid: &Grid<T>, shade: impl Fn(&T) -> u8) -> Vec<u8> {
+    let mut image = format!("P5\n{} {}\n255\n", grid.width(), grid.height()).into_bytes();
+    image.extend(grid.iter().map(shade));
+    image
+}
+
+/// A colour image, with `colour` turning each cell into red, green and blue.
+pub fn ppm<T>(grid: &Grid<T>, colour: impl Fn(&T) -> [u8; 3]) -> Vec<u8> {
+    let mut image = format!("P6\n{} {}\n255\n", grid.width(), grid.height()).into_bytes();
+    for cell in grid.iter() {
+        image.extend_from_slice(&colour(cell));
+    }
+    image
+}
+
+/// Scales `value` from 0..=max to 0..=255.
+pub fn scale(value: u64, max: u64) -> u8 {
+    if max == 0 {
+        return 0;
+    }
+    (value.min(max) * 255 / max) as u8
+}
+
+/// A heatmap colour for `value` out of `max`: black for nothing, then from dark
+/// red through orange up to yellow-white for the most.
+pub fn heat(value: u64, max: u64) -> [u8; 3] {
+    if value == 0 {
+        return [0, 0, 0];
+    }
+    let level = scale(value, max) as u32;
+    let red = 96 + level * 159 / 255;
+    let green = level;
+    let blue = level.saturating_sub(192) * 4;
+    [red as u8, green as u8, blue.min(255) as u8]
+}
+
+/// Numbered images in a directory, e.g. life_0000.pgm, life_0001.pgm, ..., one
+/// per step of an animation.
+pub struct Frames {
+    dir: PathBuf,
+    prefix: String,
+    extension: &'static str,
+    count: usize,
+}
+
+impl Frames {
+    /// Creates `dir` if it doesn't exist yet.
+    pub fn new(dir: &Path, prefix: &str, extension: &'static str) -> io::Result<Frames> {
+        fs::create_dir_all(dir)?;
+        Ok(Frames { dir: dir.to_path_buf(), prefix: prefix.to_string(), extension, count: 0 })
+    }
+
+    /// Writes the next frame and returns its path.
+    pub fn write(&mut self, image: &[u8]) -> io::Result<PathBuf> {
+        let path = self.dir.join(format!("{}_{:04}.{}", self.prefix, self.count, self.extension));
+        fs::write(&path, image)?;
+        self.count += 1;
+        Ok(path)
+    }
+
+    /// How many frames have been written.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
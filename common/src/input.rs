@@ -0,0 +1,187 @@
+//! Loading puzzle input from a file, stdin or a string.
+//!
+//! Every source goes through the same checks: the bytes must be UTF-8, trailing
+//! newlines are trimmed, and there must be something left afterwards.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+
+#[derive(Debug)]
+/// Why an input couldn't be loaded.
+pub enum InputError {
+    /// The input file does not exist.
+    Missing { path: String },
+    /// Any other failure while reading.
+    Io { name: String, error: io::Error },
+    /// The input contains bytes that are not UTF-8. `valid_up_to` is the byte
+    /// offset of the first bad byte.
+    NotUtf8 { name: String, valid_up_to: usize },
+    /// Nothing but whitespace.
+    Empty { name: String },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InputError::Missing { ref path } => write!(f, "input file {} does not exist", path),
+            InputError::Io { ref name, ref error } => write!(f, "couldn't read {}: {}", name, error),
+            InputError::NotUtf8 { ref name, valid_up_to } => {
+                write!(f, "{} is not valid UTF-8 (bad byte at offset {})", name, valid_up_to)
+            }
+            InputError::Empty { ref name } => write!(f, "{} is empty", name),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            InputError::Io { ref error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Puzzle input text, along with a name for it to use in messages and any
+/// named parameters given on the command line.
+#[derive(Clone, Debug)]
+pub struct PuzzleInput {
+    name: String,
+    text: String,
+    params: Vec<(String, String)>,
+}
+
+impl PuzzleInput {
+    /// Reads the file at `path`, which is also the input's name.
+    pub fn from_path(path: &str) -> Result<PuzzleInput, InputError> {
+        let bytes = fs::read(path).map_err(|error| match error.kind() {
+            io::ErrorKind::NotFound => InputError::Missing { path: path.to_string() },
+            _ => InputError::Io { name: path.to_string(), error },
+        })?;
+        PuzzleInput::from_bytes(path, bytes)
+    }
+
+    /// Reads all of stdin.
+    pub fn from_stdin() -> Result<PuzzleInput, InputError> {
+        let name = "<stdin>";
+        let mut bytes = Vec::new();
+        io::stdin().read_to_end(&mut bytes)
+                   .map_err(|error| InputError::Io { name: name.to_string(), error })?;
+        PuzzleInput::from_bytes(name, bytes)
+    }
+
+    /// Input that's already in memory, e.g. an example in a test.
+    pub fn from_text(name: &str, text: &str) -> Result<PuzzleInput, InputError> {
+        PuzzleInput::from_bytes(name, text.as_bytes().to_vec())
+    }
+
+    fn from_bytes(name: &str, bytes: Vec<u8>) -> Result<PuzzleInput, InputError> {
+        let mut text = String::from_utf8(bytes).map_err(|error| InputError::NotUtf8 {
+            name: name.to_string(),
+            valid_up_to: error.utf8_error().valid_up_to(),
+        })?;
+
+        let trimmed_len = text.trim_end_matches(&['\n', '\r'][..]).len();
+        text.truncate(trimmed_len);
+
+        if text.trim().is_empty() {
+            return Err(InputError::Empty { name: name.to_string() });
+        }
+
+        Ok(PuzzleInput { name: name.to_string(), text, params: Vec::new() })
+    }
+
+    /// Adds a named parameter, for puzzle settings that are part of the puzzle
+    /// text rather than the input (like how long day 14's race lasts).
+    pub fn with_param(mut self, key: &str, value: &str) -> PuzzleInput {
+        self.params.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// The last value given for `key`, if any.
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.params.iter()
+                   .rev()
+                   .find(|&(k, _)| k == key)
+                   .map(|(_, value)| value.as_str())
+    }
+
+    /// Path, `<stdin>`, or whatever name the text was given.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The whole input, without trailing newlines.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Every line of the input.
+    pub fn lines(&self) -> std::str::Lines<'_> {
+        self.text.lines()
+    }
+
+    /// 64-bit FNV-1a hash of the text and parameters. Unlike std's
+    /// `DefaultHasher` this never changes between builds, so it can be written
+    /// to files and compared later. Without parameters only the text is hashed.
+    pub fn hash(&self) -> u64 {
+        let mut bytes: Vec<u8> = self.text.bytes().collect();
+        for (key, value) in &self.params {
+            bytes.extend(format!("\n{}={}", key, value).bytes());
+        }
+        bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_files() {
+        match PuzzleInput::from_path("no/such/input.txt") {
+            Err(InputError::Missing { path }) => assert_eq!(path, "no/such/input.txt"),
+            other => panic!("expected a missing file, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn bytes_must_be_utf8() {
+        let error = PuzzleInput::from_bytes("bytes", b"ok\n\xff".to_vec()).unwrap_err();
+        assert!(matches!(error, InputError::NotUtf8 { valid_up_to: 3, .. }));
+        assert_eq!(error.to_string(), "bytes is not valid UTF-8 (bad byte at offset 3)");
+    }
+
+    #[test]
+    fn blank_input_is_empty() {
+        for text in &["", "\n", " \r\n\t\n"] {
+            let error = PuzzleInput::from_text("blank", text).unwrap_err();
+            assert!(matches!(error, InputError::Empty { .. }), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn trailing_newlines_are_trimmed() {
+        let input = PuzzleInput::from_text("crlf", "1 2\r\n3 4\r\n\r\n").unwrap();
+        assert_eq!(input.text(), "1 2\r\n3 4");
+        assert_eq!(input.lines().collect::<Vec<&str>>(), ["1 2", "3 4"]);
+
+        // Leading whitespace is part of the input.
+        assert_eq!(PuzzleInput::from_text("indented", "  x\n").unwrap().text(), "  x");
+    }
+
+    #[test]
+    fn params_change_the_hash() {
+        let input = PuzzleInput::from_text("race", "Comet").unwrap();
+        let longer = input.clone().with_param("seconds", "1000");
+        assert_eq!(longer.param("seconds"), Some("1000"));
+        assert_eq!(longer.clone().with_param("seconds", "5").param("seconds"), Some("5"));
+        assert_eq!(input.param("seconds"), None);
+        assert_ne!(input.hash(), longer.hash());
+        assert_eq!(input.hash(), PuzzleInput::from_text("other name", "Comet\n").unwrap().hash());
+    }
+}
@@ -0,0 +1,78 @@
+//! Shared plumbing for the day crates and the aoc2015 runner.
+
+use std::fmt::Display;
+
+pub mod differential;
+pub mod grid;
+pub mod image;
+pub mod input;
+pub mod parse;
+pub mod random;
+
+pub use differential::{Differential, Mismatch};
+pub use grid::{Grid, Point, SparseGrid};
+pub use input::{InputError, PuzzleInput};
+pub use parse::{LoadError, ParseError, Token, Tokens};
+pub use random::{Generator, Rng};
+
+/// A single day's puzzle, split into a parse stage and the two solving stages
+/// so callers can run (and time) each stage independently.
+pub trait Solution {
+    /// Parsed puzzle input, shared by both parts.
+    type Input;
+    /// Part 1's answer.
+    type Part1: Display;
+    /// Part 2's answer.
+    type Part2: Display;
+
+    /// Bump when a change to the solver could change its answers, so answers
+    /// cached by the runner for older versions aren't reused.
+    const VERSION: u32 = 1;
+
+    /// Checks and parses the whole input.
+    fn parse(input: &PuzzleInput) -> Result<Self::Input, ParseError>;
+    /// Solves part 1 from a parsed input.
+    fn part1(input: &Self::Input) -> Self::Part1;
+    /// Solves part 2 from a parsed input.
+    fn part2(input: &Self::Input) -> Self::Part2;
+}
+
+/// Type-erased view of a parsed input, so days with different input types can
+/// live in the same table.
+pub trait Solver {
+    /// Part 1's answer, as text.
+    fn part1(&self) -> String;
+    /// Part 2's answer, as text.
+    fn part2(&self) -> String;
+
+    /// Part 1 or 2's answer, or None for any other part.
+    fn part(&self, part: u32) -> Option<String> {
+        match part {
+            1 => Some(self.part1()),
+            2 => Some(self.part2()),
+            _ => None,
+        }
+    }
+}
+
+struct Parsed<S: Solution>(S::Input);
+
+impl<S: Solution> Solver for Parsed<S> {
+    fn part1(&self) -> String {
+        S::part1(&self.0).to_string()
+    }
+
+    fn part2(&self) -> String {
+        S::part2(&self.0).to_string()
+    }
+}
+
+/// Parses `input` with `S` and hides the input type behind a `Solver`.
+pub fn parse<S: Solution + 'static>(input: &PuzzleInput) -> Result<Box<dyn Solver>, ParseError> {
+    Ok(Box::new(Parsed::<S>(S::parse(input)?)))
+}
+
+/// Generates an input for `G` from `seed`, at `G`'s default size if none is given.
+pub fn generate<G: Generator>(seed: u64, size: Option<usize>) -> String {
+    G::generate(&mut Rng::new(seed), size.unwrap_or(G::DEFAULT_SIZE))
+}
@@ -0,0 +1,378 @@
+//! Position-aware parse errors and a small tokenizer for line-based inputs.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::input::{InputError, PuzzleInput};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Where an input stopped making sense, and what was expected there.
+pub struct ParseError {
+    /// Path or name of the input
+    pub file: String,
+    /// 1-based line and column (in characters)
+    pub line: usize,
+    pub column: usize,
+    /// Number of characters to underline
+    pub width: usize,
+    /// What the parser wanted, e.g. "a number" or "'through'"
+    pub expected: String,
+    /// What it got instead, e.g. "'x'" or "end of line"
+    pub found: String,
+    /// The whole offending line, for diagnostics
+    pub source_line: String,
+}
+
+impl ParseError {
+    /// Multi-line, compiler-style report:
+    ///
+    /// ```text
+    /// expected a number, found 'x'
+    ///  --> day2.txt:3:3
+    ///   |
+    /// 3 | 2xx4
+    ///   |   ^
+    /// ```
+    pub fn diagnostic(&self) -> String {
+        let gutter = " ".repeat(self.line.to_string().len());
+        format!("expected {}, found {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}{}",
+                self.expected,
+                self.found,
+                gutter,
+                self.file,
+                self.line,
+                self.column,
+                gutter,
+                self.line,
+                self.source_line,
+                gutter,
+                " ".repeat(self.column - 1),
+                "^".repeat(self.width.max(1)))
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}:{}: expected {}, found {}",
+               self.file, self.line, self.column, self.expected, self.found)
+    }
+}
+
+impl Error for ParseError {}
+
+/// A piece of a line, remembering where it came from.
+#[derive(Clone, Copy, Debug)]
+pub struct Token<'a> {
+    pub text: &'a str,
+    /// Byte offset into the line
+    pub offset: usize,
+}
+
+impl<'a> Token<'a> {
+    /// Drops trailing punctuation, e.g. the comma in "a,".
+    pub fn trim_end(self, chars: &[char]) -> Token<'a> {
+        Token { text: self.text.trim_end_matches(chars), offset: self.offset }
+    }
+
+    /// Drops whitespace from both ends, e.g. the spaces in " 2 ".
+    pub fn trim(self) -> Token<'a> {
+        let start = self.text.len() - self.text.trim_start().len();
+        Token { text: self.text.trim(), offset: self.offset + start }
+    }
+
+    /// Splits on `separator`, e.g. "2x3x4" into "2", "3" and "4".
+    pub fn split(self, separator: char) -> Vec<Token<'a>> {
+        let mut offset = self.offset;
+        self.text.split(separator)
+                 .map(|text| {
+                     let token = Token { text, offset };
+                     offset += text.len() + separator.len_utf8();
+                     token
+                 })
+                 .collect()
+    }
+}
+
+/// One line of input, consumed a whitespace-separated token at a time.
+pub struct Tokens<'a> {
+    file: &'a str,
+    line: usize,
+    text: &'a str,
+    offset: usize,
+}
+
+impl<'a> Tokens<'a> {
+    /// Line `line` of `file`, whose text is `text`.
+    pub fn new(file: &'a str, line: usize, text: &'a str) -> Tokens<'a> {
+        Tokens { file, line, text, offset: 0 }
+    }
+
+    /// 1-based line number
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The whole line.
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// Next token, or an error naming what was expected if the line is done.
+    pub fn next(&mut self, expected: &str) -> Result<Token<'a>, ParseError> {
+        self.next_token().ok_or_else(|| self.error_at_end(expected))
+    }
+
+    /// Everything left on the line as one token, without the whitespace around
+    /// it, e.g. "2 x 3 x 4". An error naming what was expected if the line is
+    /// done.
+    pub fn rest(&mut self, expected: &str) -> Result<Token<'a>, ParseError> {
+        let rest = Token { text: &self.text[self.offset..], offset: self.offset }.trim();
+        self.offset = self.text.len();
+        if rest.text.is_empty() {
+            Err(self.error_at_end(expected))
+        } else {
+            Ok(rest)
+        }
+    }
+
+    /// Next token, which must be exactly `word`.
+    pub fn literal(&mut self, word: &str) -> Result<Token<'a>, ParseError> {
+        let expected = format!("'{}'", word);
+        let token = self.next(&expected)?;
+        if token.text == word {
+            Ok(token)
+        } else {
+            Err(self.error(token, &expected))
+        }
+    }
+
+    /// Next token, parsed as a number (or anything else `FromStr`).
+    pub fn number<T: FromStr>(&mut self, expected: &str) -> Result<T, ParseError> {
+        let token = self.next(expected)?;
+        self.parse(token, expected)
+    }
+
+    /// Parses a token already taken from the line.
+    pub fn parse<T: FromStr>(&self, token: Token<'a>, expected: &str) -> Result<T, ParseError> {
+        token.text.parse().map_err(|_| self.error(token, expected))
+    }
+
+    /// Checks that nothing is left on the line.
+    pub fn end(&mut self) -> Result<(), ParseError> {
+        match self.next_token() {
+            Some(token) => Err(self.error(token, "end of line")),
+            None => Ok(()),
+        }
+    }
+
+    /// Error pointing at `token`.
+    pub fn error(&self, token: Token<'a>, expected: &str) -> ParseError {
+        let found = if token.text.is_empty() {
+            "nothing".to_string()
+        } else {
+            format!("'{}'", token.text)
+        };
+        self.error_at(token.offset, token.text.chars().count(), expected, &found)
+    }
+
+    /// Error pointing just past the end of the line.
+    pub fn error_at_end(&self, expected: &str) -> ParseError {
+        self.error_at(self.text.len(), 1, expected, "end of line")
+    }
+
+    /// Error at a byte offset into the line.
+    pub fn error_at(&self, offset: usize, width: usize, expected: &str, found: &str) -> ParseError {
+        ParseError {
+            file: self.file.to_string(),
+            line: self.line,
+            column: self.text[..offset].chars().count() + 1,
+            width,
+            expected: expected.to_string(),
+            found: found.to_string(),
+            source_line: self.text.to_string(),
+        }
+    }
+
+    fn next_token(&mut self) -> Option<Token<'a>> {
+        let rest = &self.text[self.offset..];
+        let start = self.offset + (rest.len() - rest.trim_start().len());
+        let len = self.text[start..].find(char::is_whitespace).unwrap_or(self.text.len() - start);
+        self.offset = start + len;
+
+        if len == 0 {
+            None
+        } else {
+            Some(Token { text: &self.text[start..start + len], offset: start })
+        }
+    }
+}
+
+impl PuzzleInput {
+    /// Every line, ready to be split into tokens.
+    pub fn tokens(&self) -> impl Iterator<Item = Tokens<'_>> {
+        self.lines()
+            .enumerate()
+            .map(move |(idx, line)| Tokens::new(self.name(), idx + 1, line))
+    }
+
+    /// The first line of an input that should only have one, like day 4's
+    /// secret key.
+    pub fn single_line(&self) -> Result<Tokens<'_>, ParseError> {
+        let mut lines = self.tokens();
+        let first = lines.next().unwrap_or_else(|| self.last_line());
+
+        if let Some(mut extra) = lines.next() {
+            return Err(match extra.next_token() {
+                Some(token) => extra.error(token, "end of input"),
+                None => extra.error_at(0, 1, "end of input", "another line"),
+            });
+        }
+        Ok(first)
+    }
+
+    /// The last line, for errors about something missing from the input.
+    pub fn last_line(&self) -> Tokens<'_> {
+        let line_count = self.lines().count();
+        Tokens::new(self.name(), line_count, self.lines().last().unwrap_or(""))
+    }
+}
+
+/// Errors that stop a day from producing answers.
+pub enum LoadError {
+    Input(InputError),
+    Parse(ParseError),
+}
+
+impl From<InputError> for LoadError {
+    fn from(error: InputError) -> LoadError {
+        LoadError::Input(error)
+    }
+}
+
+impl From<ParseError> for LoadError {
+    fn from(error: ParseError) -> LoadError {
+        LoadError::Parse(error)
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LoadError::Input(ref error) => write!(f, "{}", error),
+            LoadError::Parse(ref error) => write!(f, "{}", error.diagnostic()),
+        }
+    }
+}
+
+// `main` prints the Debug form of a returned error, so show the diagnostic.
+impl fmt::Debug for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Error for LoadError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(text: &str) -> PuzzleInput {
+        PuzzleInput::from_text("example", text).unwrap()
+    }
+
+    #[test]
+    fn tokens_know_their_offsets() {
+        let mut line = Tokens::new("example", 1, "  turn on\t0,0  ");
+        let turn = line.next("a verb").unwrap();
+        assert_eq!((turn.text, turn.offset), ("turn", 2));
+        assert_eq!(line.next("a state").unwrap().offset, 7);
+
+        let corner = line.next("a corner").unwrap();
+        let coords: Vec<(&str, usize)> = corner.split(',').iter().map(|token| (token.text, token.offset)).collect();
+        assert_eq!(coords, [("0", 10), ("0", 12)]);
+        assert!(line.end().is_ok());
+    }
+
+    #[test]
+    fn trimming_keeps_offsets() {
+        let padded = Token { text: " 2 x", offset: 5 };
+        let sides: Vec<(&str, usize)> = padded.split('x').iter().map(|side| side.trim()).map(|side| (side.text, side.offset)).collect();
+        assert_eq!(sides, [("2", 6), ("", 9)]);
+
+        let comma = Token { text: "a,", offset: 3 }.trim_end(&[',']);
+        assert_eq!((comma.text, comma.offset), ("a", 3));
+    }
+
+    #[test]
+    fn rest_takes_the_whole_line() {
+        let mut line = Tokens::new("example", 1, "size  2 x 3 x 4 ");
+        line.literal("size").unwrap();
+        let rest = line.rest("dimensions").unwrap();
+        assert_eq!((rest.text, rest.offset), ("2 x 3 x 4", 6));
+        assert!(line.end().is_ok());
+
+        let error = line.rest("dimensions").unwrap_err();
+        assert_eq!((error.column, error.found.as_str()), (17, "end of line"));
+    }
+
+    #[test]
+    fn leftovers_and_missing_tokens() {
+        let mut line = Tokens::new("example", 2, "on off");
+        line.literal("on").unwrap();
+        let error = line.end().unwrap_err();
+        assert_eq!(error.to_string(), "example:2:4: expected end of line, found 'off'");
+        assert_eq!(error.width, 3);
+
+        let error = line.next("a corner").unwrap_err();
+        assert_eq!(error.to_string(), "example:2:7: expected a corner, found end of line");
+        assert_eq!(error.width, 1);
+    }
+
+    #[test]
+    fn columns_count_characters() {
+        let mut line = Tokens::new("example", 1, "café x");
+        line.next("a word").unwrap();
+        let error = line.number::<u32>("a number").unwrap_err();
+        assert_eq!((error.column, error.width), (6, 1));
+        assert_eq!(error.diagnostic(),
+                   "expected a number, found 'x'\n \
+                    --> example:1:6\n  \
+                    |\n\
+                    1 | café x\n  \
+                    |      ^");
+    }
+
+    #[test]
+    fn diagnostics_underline_the_token() {
+        let mut line = Tokens::new("day2.txt", 12, "2x3 résumé");
+        line.next("dimensions").unwrap();
+        let word = line.next("a word").unwrap();
+        assert_eq!(line.error(word, "end of line").diagnostic(),
+                   "expected end of line, found 'résumé'\n  \
+                    --> day2.txt:12:5\n   \
+                    |\n\
+                    12 | 2x3 résumé\n   \
+                    |     ^^^^^^");
+    }
+
+    #[test]
+    fn single_lines() {
+        let only = input("abcdef\n");
+        assert_eq!(only.single_line().unwrap().text(), "abcdef");
+
+        let error = input("abcdef\nghijkl").single_line().err().unwrap();
+        assert_eq!(error.to_string(), "example:2:1: expected end of input, found 'ghijkl'");
+
+        let error = input("abcdef\n   \nmore").single_line().err().unwrap();
+        assert_eq!(error.to_string(), "example:2:1: expected end of input, found another line");
+    }
+
+    #[test]
+    fn errors_after_the_last_line() {
+        let error = input("1\n22").last_line().error_at_end("a third line");
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.source_line, "22");
+    }
+}
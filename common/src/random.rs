@@ -0,0 +1,64 @@
+//! Seeded random puzzle inputs, for stress testing the solvers on more than
+//! the one personal input each day ships with.
+//!
+//! `Rng` is SplitMix64: small, fast, and the same sequence for a given seed on
+//! every platform, so a seed (plus size) is enough to reproduce any input.
+
+/// A seeded random number generator.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    /// The next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in 0..n. `n` must be positive.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "can't pick a number below zero");
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    /// A number in low..=high.
+    pub fn between(&mut self, low: i32, high: i32) -> i32 {
+        assert!(low <= high, "empty range {}..={}", low, high);
+        low + self.below((high as i64 - low as i64 + 1) as usize) as i32
+    }
+
+    /// True with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    /// One of `items`, which mustn't be empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    /// Puts `items` in a random order.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+/// Writes random, well-formed inputs in a day's official format.
+pub trait Generator {
+    /// Size used when the caller doesn't pick one, about that of a real input.
+    const DEFAULT_SIZE: usize;
+
+    /// What `size` counts differs per day: lines, characters, guests, ...
+    fn generate(rng: &mut Rng, size: usize) -> String;
+}
@@ -0,0 +1,87 @@
+// Indexes a file of instructions once, then answers queries about it read
+// from stdin, one per line:
+//
+//     after K             floor after step K
+//     first F [FROM]      first step (from step FROM on) that ends on floor F
+//     visits F A B        how many of steps A to B end on floor F
+//     lowest A B          lowest floor after any of steps A to B
+//     highest A B         highest floor after any of steps A to B
+//
+// e.g. `echo "first -5" | cargo run --bin floors -- big_input.txt`. Step 0 is
+// the ground floor before the first instruction.
+
+extern crate aoc_common;
+extern crate day_1;
+
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::str::FromStr;
+
+use aoc_common::{InputError, LoadError};
+use day_1::FloorIndex;
+
+fn number<T: FromStr>(word: Option<&str>, what: &str) -> Result<T, String> {
+	let word = word.ok_or_else(|| format!("missing {}", what))?;
+	word.parse().map_err(|_| format!("expected {}, found '{}'", what, word))
+}
+
+fn answer(index: &FloorIndex, query: &str) -> Result<String, String> {
+	let mut words = query.split_whitespace();
+	let command = words.next().unwrap_or("");
+	let answer = match command {
+		"after" => {
+			let step = number(words.next(), "a step")?;
+			index.floor_after(step).map(|floor| floor.to_string())
+		}
+		"first" => {
+			let floor = number(words.next(), "a floor")?;
+			let from = match words.next() {
+				Some(word) => number(Some(word), "a step")?,
+				None => 0,
+			};
+			Some(index.first_reaching(floor, from).map_or(String::from("never"), |step| step.to_string()))
+		}
+		"visits" => {
+			let floor = number(words.next(), "a floor")?;
+			let first = number(words.next(), "a first step")?;
+			let last = number(words.next(), "a last step")?;
+			Some(index.visits(floor, first..=last).to_string())
+		}
+		"lowest" | "highest" => {
+			let first = number(words.next(), "a first step")?;
+			let last = number(words.next(), "a last step")?;
+			let floor = if command == "lowest" {
+				index.lowest_between(first..=last)
+			} else {
+				index.highest_between(first..=last)
+			};
+			floor.map(|floor| floor.to_string())
+		}
+		_ => return Err(format!("unknown query '{}'; expected after, first, visits, lowest or highest", command)),
+	};
+	if let Some(extra) = words.next() {
+		return Err(format!("unexpected '{}' at the end of the query", extra));
+	}
+	answer.ok_or_else(|| format!("there are only {} steps", index.steps()))
+}
+
+fn main() -> Result<(), LoadError> {
+	let path = env::args().nth(1).unwrap_or_else(|| day_1::INPUT.to_string());
+	let io_error = |error| InputError::Io { name: path.clone(), error };
+	let index = FloorIndex::from_reader(BufReader::new(File::open(&path).map_err(io_error)?)).map_err(io_error)?;
+	eprintln!("Indexed {} steps of {}", index.steps(), path);
+
+	for line in io::stdin().lock().lines() {
+		let line = line.map_err(|error| InputError::Io { name: String::from("stdin"), error })?;
+		if line.trim().is_empty() {
+			continue;
+		}
+		match answer(&index, &line) {
+			Ok(answer) => println!("{}", answer),
+			Err(message) => println!("error: {}", message),
+		}
+	}
+
+	Ok(())
+}
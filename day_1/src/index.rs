@@ -0,0 +1,210 @@
+//! Answers questions about any point of a set of instructions without
+//! following them again.
+//!
+//! The index keeps the floor after every step (prefix sums of the moves), a
+//! sparse table of the lowest and highest floor over blocks of steps, and every
+//! step grouped by the floor it ended on. Each query then takes logarithmic
+//! time (plus a scan of at most one block at each end).
+//!
+//! Positions count steps taken: position 0 is the ground floor before the
+//! first step, and position k is where Santa is after step k.
+
+use std::io::{self, Read};
+use std::ops::{Range, RangeInclusive};
+
+use crate::tracker::read_steps;
+
+// Steps per block of the sparse table. The table has one entry per block on
+// each level, so bigger blocks save memory at the cost of scanning more.
+const BLOCK: usize = 256;
+
+/// The most steps a [`FloorIndex`] holds, so that every floor fits in an
+/// `i32` and every position in a `u32`.
+pub const MAX_STEPS: u64 = i32::MAX as u64;
+
+/// Floors after every step of a set of instructions, for random access.
+/// Holds up to [`MAX_STEPS`] steps.
+pub struct FloorIndex {
+	// floors[k] is the floor after step k
+	floors: Vec<i32>,
+	// lowest[j][b] is the lowest floor in blocks b..b + 2^j; highest likewise
+	lowest: Vec<Vec<i32>>,
+	highest: Vec<Vec<i32>>,
+	// Every position, sorted by floor and then by position
+	by_floor: Vec<u32>,
+	// Where each floor's positions start in `by_floor`, from the bottom floor
+	// up, with an extra end marker
+	floor_starts: Vec<u32>,
+	bottom: i32,
+}
+
+// Builds level j + 1 of a sparse table from level j.
+fn next_level(level: &[i32], width: usize, pick: fn(i32, i32) -> i32) -> Vec<i32> {
+	(0..level.len().saturating_sub(width)).map(|idx| pick(level[idx], level[idx + width])).collect()
+}
+
+impl FloorIndex {
+	/// Indexes every instruction from `reader`. Whitespace is skipped;
+	/// anything else, or more than [`MAX_STEPS`] instructions, fails with
+	/// `InvalidData`.
+	pub fn from_reader<R: Read>(reader: R) -> io::Result<FloorIndex> {
+		let mut floors = vec![0];
+		let mut floor = 0;
+		read_steps(reader, |direction| {
+			// Checked as they come, so an endless stream can't use up memory
+			if floors.len() as u64 > MAX_STEPS {
+				let message = format!("more than {} instructions to index", MAX_STEPS);
+				return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+			}
+			floor += direction as i32;
+			floors.push(floor);
+			Ok(())
+		})?;
+		Ok(FloorIndex::new(floors))
+	}
+
+	/// Indexes instructions that are already in memory.
+	pub fn from_bytes(bytes: &[u8]) -> io::Result<FloorIndex> {
+		FloorIndex::from_reader(bytes)
+	}
+
+	fn new(floors: Vec<i32>) -> FloorIndex {
+		let block_range = |pick: fn(i32, i32) -> i32| -> Vec<i32> {
+			floors.chunks(BLOCK).map(|block| block.iter().cloned().fold(block[0], pick)).collect()
+		};
+		let mut lowest = vec![block_range(i32::min)];
+		let mut highest = vec![block_range(i32::max)];
+		let mut width = 1;
+		while width * 2 <= lowest[0].len() {
+			let next = next_level(lowest.last().expect("level 0"), width, i32::min);
+			lowest.push(next);
+			let next = next_level(highest.last().expect("level 0"), width, i32::max);
+			highest.push(next);
+			width *= 2;
+		}
+
+		// Counting sort of the positions by floor; each floor's positions stay in
+		// order since they're added in order.
+		let bottom = lowest[0].iter().cloned().min().unwrap_or(0);
+		let top = highest[0].iter().cloned().max().unwrap_or(0);
+		let mut floor_starts = vec![0u32; (top - bottom) as usize + 2];
+		for &floor in &floors {
+			floor_starts[(floor - bottom) as usize + 1] += 1;
+		}
+		for idx in 1..floor_starts.len() {
+			floor_starts[idx] += floor_starts[idx - 1];
+		}
+		let mut next = floor_starts.clone();
+		let mut by_floor = vec![0; floors.len()];
+		for (position, &floor) in floors.iter().enumerate() {
+			let slot = &mut next[(floor - bottom) as usize];
+			by_floor[*slot as usize] = position as u32;
+			*slot += 1;
+		}
+
+		FloorIndex { floors, lowest, highest, by_floor, floor_starts, bottom }
+	}
+
+	/// How many steps were indexed.
+	pub fn steps(&self) -> u64 {
+		self.floors.len() as u64 - 1
+	}
+
+	/// The floor after `step`, or None past the last step.
+	pub fn floor_after(&self, step: u64) -> Option<i32> {
+		self.floors.get(step as usize).cloned()
+	}
+
+	fn check_range(&self, steps: &RangeInclusive<u64>) -> Option<(usize, usize)> {
+		let (&first, &last) = (steps.start(), steps.end());
+		if first > last || last > self.steps() {
+			return None;
+		}
+		Some((first as usize, last as usize))
+	}
+
+	// `pick` of the floors at positions first..=last, using `table` for the
+	// whole blocks in between.
+	fn range(&self, first: usize, last: usize, table: &[Vec<i32>], pick: fn(i32, i32) -> i32) -> i32 {
+		let scan = |range: RangeInclusive<usize>| self.floors[range].iter().cloned().fold(self.floors[first], pick);
+		let (first_block, last_block) = (first / BLOCK, last / BLOCK);
+		if last_block - first_block < 2 {
+			return scan(first..=last);
+		}
+
+		let ends = pick(scan(first..=(first_block + 1) * BLOCK - 1), scan(last_block * BLOCK..=last));
+		// Two overlapping power-of-two runs of blocks cover the middle
+		let (from, to) = (first_block + 1, last_block - 1);
+		let level = (usize::BITS - 1 - (to - from + 1).leading_zeros()) as usize;
+		let middle = pick(table[level][from], table[level][to + 1 - (1 << level)]);
+		pick(ends, middle)
+	}
+
+	/// The lowest floor Santa is on after any of `steps`, or None if the range
+	/// is empty or goes past the last step.
+	pub fn lowest_between(&self, steps: RangeInclusive<u64>) -> Option<i32> {
+		let (first, last) = self.check_range(&steps)?;
+		Some(self.range(first, last, &self.lowest, i32::min))
+	}
+
+	/// The highest floor Santa is on after any of `steps`.
+	pub fn highest_between(&self, steps: RangeInclusive<u64>) -> Option<i32> {
+		let (first, last) = self.check_range(&steps)?;
+		Some(self.range(first, last, &self.highest, i32::max))
+	}
+
+	/// The first step from `from` on after which Santa is on `floor`, or None
+	/// if that never happens. Step 0 is the ground floor before the first step.
+	pub fn first_reaching(&self, floor: i32, from: u64) -> Option<u64> {
+		let start = self.floor_after(from)?;
+		// Santa moves one floor at a time, so the first step at or past `floor`
+		// (in the direction of `floor`) is the first step on it.
+		let (past, table): (fn(i32, i32) -> bool, _) = if floor <= start {
+			(|floor, target| floor <= target, &self.lowest)
+		} else {
+			(|floor, target| floor >= target, &self.highest)
+		};
+		let scan = |mut positions: Range<usize>| {
+			positions.find(|&position| past(self.floors[position], floor)).map(|position| position as u64)
+		};
+
+		let from = from as usize;
+		let block = from / BLOCK;
+		let block_end = ((block + 1) * BLOCK).min(self.floors.len());
+		if let Some(step) = scan(from..block_end) {
+			return Some(step);
+		}
+
+		// Skip whole runs of blocks that never get there, largest first
+		let blocks = table[0].len();
+		let mut block = block + 1;
+		for level in (0..table.len()).rev() {
+			let width = 1 << level;
+			if block + width <= blocks && !past(table[level][block], floor) {
+				block += width;
+			}
+		}
+		if block == blocks {
+			return None;
+		}
+		scan(block * BLOCK..((block + 1) * BLOCK).min(self.floors.len()))
+	}
+
+	/// How many of `steps` end on `floor`.
+	pub fn visits(&self, floor: i32, steps: RangeInclusive<u64>) -> u64 {
+		let (first, last) = match self.check_range(&steps) {
+			Some(range) => range,
+			None => return 0,
+		};
+		// In i64, so floors far from any step can't overflow
+		let idx = floor as i64 - self.bottom as i64;
+		if idx < 0 || idx + 1 >= self.floor_starts.len() as i64 {
+			return 0;
+		}
+		let idx = idx as usize;
+		let positions = &self.by_floor[self.floor_starts[idx] as usize..self.floor_starts[idx + 1] as usize];
+		let before = positions.partition_point(|&position| (position as usize) < first);
+		let through = positions.partition_point(|&position| (position as usize) <= last);
+		(through - before) as u64
+	}
+}
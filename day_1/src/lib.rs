@@ -0,0 +1,193 @@
+//! Day 1: Not Quite Lisp
+//!
+//! Santa follows parentheses up and down the floors of a building.
+//! [`FloorTracker`] follows them from a stream, keeping statistics about every
+//! floor on the way, and [`FloorIndex`] answers questions about any step
+//! without following them again.
+
+extern crate aoc_common;
+
+pub mod index;
+pub mod tracker;
+
+use aoc_common::{Generator, ParseError, PuzzleInput, Rng, Solution};
+
+pub use index::FloorIndex;
+pub use tracker::{BadInstruction, BasementVisit, FloorStats, FloorTracker};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day_1_1_input.txt");
+
+// Follows every instruction in an input that has already been checked.
+fn follow_instructions(input_string: &str) -> FloorTracker {
+	let mut tracker = FloorTracker::new();
+	tracker.feed(input_string.as_bytes()).expect("parse only accepts '(' and ')'");
+	tracker
+}
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day1;
+
+impl Solution for Day1 {
+	type Input = String;
+	type Part1 = i32;
+	type Part2 = i32;
+
+	fn parse(input: &PuzzleInput) -> Result<String, ParseError> {
+		for line in input.tokens() {
+			for (offset, c) in line.text().char_indices() {
+				if c != '(' && c != ')' {
+					return Err(line.error_at(offset, 1, "'(' or ')'", &format!("'{}'", c)));
+				}
+			}
+		}
+		Ok(input.text().to_string())
+	}
+
+	fn part1(input_string: &String) -> i32 {
+		follow_instructions(input_string).floor() as i32
+	}
+
+	fn part2(input_string: &String) -> i32 {
+		// 0 if the basement is never entered
+		follow_instructions(input_string).first_basement_step().unwrap_or(0) as i32
+	}
+}
+
+// `size` parentheses, as a random walk that usually dips into the basement.
+impl Generator for Day1 {
+	const DEFAULT_SIZE: usize = 7000;
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		(0..size).map(|_| if rng.chance(0.5) { '(' } else { ')' }).collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn example(text: &str) -> String {
+		Day1::parse(&PuzzleInput::from_text("example", text).unwrap()).unwrap()
+	}
+
+	#[test]
+	fn part1_examples() {
+		let cases = [
+			("(())", 0), ("()()", 0),
+			("(((", 3), ("(()(()(", 3), ("))(((((", 3),
+			("())", -1), ("))(", -1),
+			(")))", -3), (")())())", -3),
+		];
+		for &(text, floor) in &cases {
+			assert_eq!(Day1::part1(&example(text)), floor, "{}", text);
+		}
+	}
+
+	#[test]
+	fn part2_examples() {
+		assert_eq!(Day1::part2(&example(")")), 1);
+		assert_eq!(Day1::part2(&example("()())")), 5);
+	}
+
+	#[test]
+	fn generated_inputs_solve() {
+		for seed in 0..20 {
+			let text = aoc_common::generate::<Day1>(seed, Some(500));
+			let input = example(&text);
+			let floor = text.matches('(').count() as i32 - text.matches(')').count() as i32;
+			assert_eq!(Day1::part1(&input), floor);
+			let basement = text.chars()
+				.scan(0, |floor, c| { *floor += if c == '(' { 1 } else { -1 }; Some(*floor) })
+				.position(|floor| floor == -1)
+				.map_or(0, |idx| idx as i32 + 1);
+			assert_eq!(Day1::part2(&input), basement);
+		}
+	}
+
+	#[test]
+	fn tracker_statistics() {
+		let mut tracker = FloorTracker::new();
+		tracker.feed(b"))(((()))\n)").unwrap();
+		assert_eq!(tracker.floor(), -2);
+		assert_eq!(tracker.steps(), 10);
+		assert_eq!((tracker.lowest(), tracker.highest()), (-2, 2));
+		assert_eq!(tracker.basement_visits(),
+		           &[BasementVisit { entered: 1, left: Some(4) }, BasementVisit { entered: 9, left: None }]);
+		assert_eq!(tracker.first_basement_step(), Some(1));
+		assert_eq!(tracker.first_reached(-2), Some(2));
+		assert_eq!(tracker.first_reached(2), Some(6));
+		assert_eq!(tracker.first_reached(0), Some(0));
+		assert_eq!(tracker.first_reached(3), None);
+		assert_eq!(tracker.steps_on(-1), 3);
+		assert_eq!(tracker.steps_on(5), 0);
+		let total: u64 = tracker.floors().map(|(_, stats)| stats.steps).sum();
+		assert_eq!(total, tracker.steps());
+	}
+
+	#[test]
+	fn tracker_streams_from_readers() {
+		// Reads a byte at a time, to split the stream at every position.
+		struct Trickle<'a>(&'a [u8]);
+		impl<'a> std::io::Read for Trickle<'a> {
+			fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+				match self.0.split_first() {
+					Some((&byte, rest)) if !buffer.is_empty() => {
+						buffer[0] = byte;
+						self.0 = rest;
+						Ok(1)
+					}
+					_ => Ok(0),
+				}
+			}
+		}
+
+		let text = aoc_common::generate::<Day1>(3, Some(5000));
+		let tracker = FloorTracker::from_reader(Trickle(text.as_bytes())).unwrap();
+		assert_eq!(tracker.floor() as i32, Day1::part1(&example(&text)));
+		assert_eq!(tracker.first_basement_step().unwrap_or(0) as i32, Day1::part2(&example(&text)));
+
+		let error = FloorTracker::from_reader(&b"(()\n)x("[..]).unwrap_err();
+		assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+		assert_eq!(error.to_string(), "expected '(' or ')' at byte 5, found 'x'");
+	}
+
+	#[test]
+	fn index_matches_scanning() {
+		// Long enough for several levels of blocks
+		let text = aoc_common::generate::<Day1>(7, Some(5000));
+		let index = FloorIndex::from_bytes(text.as_bytes()).unwrap();
+		let floors: Vec<i32> = Some(0).into_iter()
+		                              .chain(text.bytes().scan(0, |floor, byte| {
+			                              *floor += if byte == b'(' { 1 } else { -1 };
+			                              Some(*floor)
+		                              }))
+		                              .collect();
+		assert_eq!(index.steps(), 5000);
+		assert_eq!(index.floor_after(5000), floors.last().cloned());
+		assert_eq!(index.floor_after(5001), None);
+
+		let mut rng = Rng::new(1);
+		for _ in 0..300 {
+			let first = rng.below(5001);
+			let last = first + rng.below(5001 - first);
+			let steps = first as u64..=last as u64;
+			let range = &floors[first..=last];
+			assert_eq!(index.lowest_between(steps.clone()), range.iter().cloned().min());
+			assert_eq!(index.highest_between(steps.clone()), range.iter().cloned().max());
+
+			let floor = floors[rng.below(5001)] + rng.below(5) as i32 - 2;
+			let visits = range.iter().filter(|&&on| on == floor).count() as u64;
+			assert_eq!(index.visits(floor, steps), visits);
+			let reaching = floors[first..].iter().position(|&on| on == floor).map(|step| (first + step) as u64);
+			assert_eq!(index.first_reaching(floor, first as u64), reaching, "floor {} from {}", floor, first);
+		}
+
+		assert_eq!(index.first_reaching(-1, 0).unwrap_or(0) as i32, Day1::part2(&example(&text)));
+		assert_eq!(index.lowest_between(10..=5001), None);
+		assert_eq!(index.visits(10_000, 0..=5000), 0);
+		assert_eq!(index.visits(i32::MAX, 0..=5000), 0);
+		assert_eq!(index.visits(i32::MIN, 0..=5000), 0);
+		assert_eq!(index.first_reaching(i32::MIN, 0), None);
+	}
+}
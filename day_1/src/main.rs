@@ -1,40 +1,30 @@
+extern crate aoc_common;
+extern crate day_1;
 
-use std::io;
-use std::io::prelude::*;
+use std::env;
 use std::fs::File;
+use std::io::BufReader;
 
-fn read_file(filename: String) -> Result<String, io::Error> {
-	let mut f = try!(File::open(filename));
-	let mut s = String::new();
-	try!(f.read_to_string(&mut s));
-	return Ok(s)
-}
+use aoc_common::{InputError, LoadError, PuzzleInput, Solution};
+use day_1::{Day1, FloorTracker};
+
+// Solves the bundled input, then streams the instructions from a file (the
+// first argument, or the bundled input) and prints statistics about them.
+fn main() -> Result<(), LoadError> {
+	let input = Day1::parse(&PuzzleInput::from_path(day_1::INPUT)?)?;
+	println!("day 1 part 1: {}", Day1::part1(&input));
+	println!("day 1 part 2: {}", Day1::part2(&input));
+
+	let path = env::args().nth(1).unwrap_or_else(|| day_1::INPUT.to_string());
+	let io_error = |error| InputError::Io { name: path.clone(), error };
+	let tracker = FloorTracker::from_reader(BufReader::new(File::open(&path).map_err(io_error)?)).map_err(io_error)?;
 
-fn main() {
-    println!("Hello, world!");
-	
-	let filename = "day_1_1_input.txt".to_string();
-	let input = read_file(filename);
-	assert!(input.is_ok());
-	let input_string = input.unwrap();
-	
-	let mut floor = 0;
-	let mut step_idx = 0;
-	let mut first_basement_step = 0;
-	
-	for c in input_string.chars() {
-		step_idx += 1;
-		match c {
-			'(' => floor += 1,
-			')' => floor -= 1,
-			_ => { /* ignore everything else */ }
-		}
-		
-		if floor < 0 && first_basement_step == 0 {
-			first_basement_step = step_idx
-		}
+	println!("{} steps, ending on floor {}", tracker.steps(), tracker.floor());
+	println!("floors {} to {}", tracker.lowest(), tracker.highest());
+	println!("{} trips into the basement", tracker.basement_visits().len());
+	if let Some((floor, stats)) = tracker.floors().max_by_key(|&(_, stats)| stats.steps) {
+		println!("most steps on floor {}: {}", floor, stats.steps);
 	}
-	
-	println!("Final floor is {}", floor);
-	println!("Entered basement on step {}", first_basement_step);	
-}
\ No newline at end of file
+
+	Ok(())
+}
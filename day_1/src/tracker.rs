@@ -0,0 +1,219 @@
+//! Follows instructions one at a time, keeping statistics about every floor.
+//!
+//! Nothing but the statistics is kept, so instructions can be streamed from a
+//! reader of any size. Memory grows with the number of floors visited and the
+//! number of trips into the basement, not with the number of steps.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, Read};
+
+/// What happened on one floor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FloorStats {
+	/// The step on which the floor was first reached; 0 for the ground floor.
+	pub first_reached: u64,
+	/// How many steps ended on this floor.
+	pub steps: u64,
+}
+
+/// One trip into the basement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BasementVisit {
+	/// The step that went down to floor -1.
+	pub entered: u64,
+	/// The step that came back up to the ground floor, or None if Santa is
+	/// still down there.
+	pub left: Option<u64>,
+}
+
+/// A byte that isn't an instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BadInstruction {
+	/// Byte offset from the start of everything fed to the tracker.
+	pub offset: u64,
+	pub byte: u8,
+}
+
+impl fmt::Display for BadInstruction {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "expected '(' or ')' at byte {}, found {:?}", self.offset, self.byte as char)
+	}
+}
+
+impl std::error::Error for BadInstruction {}
+
+// The move for one byte of input: 1 or -1 for an instruction, 0 for
+// whitespace and None for anything else.
+fn direction(byte: u8) -> Option<i64> {
+	match byte {
+		b'(' => Some(1),
+		b')' => Some(-1),
+		b' ' | b'\t' | b'\r' | b'\n' => Some(0),
+		_ => None,
+	}
+}
+
+// Reads instructions from `reader` in fixed-size chunks, calling `step` with
+// the direction of each one. Whitespace is skipped; anything else fails with
+// `InvalidData`. An error from `step` stops the reading too.
+pub(crate) fn read_steps<R: Read>(mut reader: R, mut step: impl FnMut(i64) -> io::Result<()>) -> io::Result<()> {
+	let mut buffer = vec![0; 64 * 1024];
+	let mut offset = 0;
+	loop {
+		let read = match reader.read(&mut buffer) {
+			Ok(0) => return Ok(()),
+			Ok(read) => read,
+			Err(ref error) if error.kind() == io::ErrorKind::Interrupted => continue,
+			Err(error) => return Err(error),
+		};
+		for &byte in &buffer[..read] {
+			match direction(byte) {
+				Some(0) => {}
+				Some(direction) => step(direction)?,
+				None => {
+					let error = BadInstruction { offset, byte };
+					return Err(io::Error::new(io::ErrorKind::InvalidData, error));
+				}
+			}
+			offset += 1;
+		}
+	}
+}
+
+/// Santa's position and statistics so far. Steps are numbered from 1, as in
+/// the puzzle.
+#[derive(Clone, Debug)]
+pub struct FloorTracker {
+	floor: i64,
+	steps: u64,
+	// Bytes fed so far, including whitespace, for error offsets
+	bytes: u64,
+	lowest: i64,
+	// Every floor from `lowest` up; Santa only ever moves one floor at a
+	// time, so there are no gaps.
+	floors: VecDeque<FloorStats>,
+	basement: Vec<BasementVisit>,
+}
+
+impl Default for FloorTracker {
+	fn default() -> FloorTracker {
+		FloorTracker::new()
+	}
+}
+
+impl FloorTracker {
+	/// Santa on the ground floor, before the first step.
+	pub fn new() -> FloorTracker {
+		FloorTracker {
+			floor: 0,
+			steps: 0,
+			bytes: 0,
+			lowest: 0,
+			floors: VecDeque::from(vec![FloorStats { first_reached: 0, steps: 0 }]),
+			basement: Vec::new(),
+		}
+	}
+
+	/// Follows every instruction from `reader` in fixed-size chunks.
+	/// Whitespace is skipped; anything else fails with `InvalidData`.
+	pub fn from_reader<R: Read>(reader: R) -> io::Result<FloorTracker> {
+		let mut tracker = FloorTracker::new();
+		read_steps(reader, |direction| {
+			tracker.step(direction);
+			Ok(())
+		})?;
+		Ok(tracker)
+	}
+
+	/// Follows the instructions in `bytes`, which may be any part of a longer
+	/// stream. Whitespace is skipped. Stops at the first byte that isn't an
+	/// instruction, having followed everything before it.
+	pub fn feed(&mut self, bytes: &[u8]) -> Result<(), BadInstruction> {
+		for &byte in bytes {
+			match direction(byte) {
+				Some(0) => {}
+				Some(direction) => self.step(direction),
+				None => return Err(BadInstruction { offset: self.bytes, byte }),
+			}
+			self.bytes += 1;
+		}
+		Ok(())
+	}
+
+	/// Moves one floor up (1) or down (-1).
+	pub fn step(&mut self, direction: i64) {
+		debug_assert!(direction == 1 || direction == -1);
+		self.steps += 1;
+		self.floor += direction;
+
+		let new_floor = FloorStats { first_reached: self.steps, steps: 0 };
+		if self.floor < self.lowest {
+			self.lowest = self.floor;
+			self.floors.push_front(new_floor);
+		} else if self.floor > self.highest() {
+			self.floors.push_back(new_floor);
+		}
+		let idx = (self.floor - self.lowest) as usize;
+		self.floors[idx].steps += 1;
+
+		if self.floor == -1 && direction == -1 {
+			self.basement.push(BasementVisit { entered: self.steps, left: None });
+		} else if self.floor == 0 && direction == 1 {
+			let visit = self.basement.last_mut().expect("came up from the basement");
+			visit.left = Some(self.steps);
+		}
+	}
+
+	/// The floor Santa is on now.
+	pub fn floor(&self) -> i64 {
+		self.floor
+	}
+
+	/// How many instructions have been followed.
+	pub fn steps(&self) -> u64 {
+		self.steps
+	}
+
+	pub fn lowest(&self) -> i64 {
+		self.lowest
+	}
+
+	pub fn highest(&self) -> i64 {
+		self.lowest + self.floors.len() as i64 - 1
+	}
+
+	/// Every trip into the basement, in order.
+	pub fn basement_visits(&self) -> &[BasementVisit] {
+		&self.basement
+	}
+
+	/// The step that first entered the basement, if any did.
+	pub fn first_basement_step(&self) -> Option<u64> {
+		self.basement.first().map(|visit| visit.entered)
+	}
+
+	/// Statistics for `floor`, or None if Santa never reached it.
+	pub fn stats(&self, floor: i64) -> Option<FloorStats> {
+		if floor < self.lowest {
+			return None;
+		}
+		self.floors.get((floor - self.lowest) as usize).cloned()
+	}
+
+	/// The first step on which Santa reached `floor`, or None if that never
+	/// happened. The ground floor is reached on step 0.
+	pub fn first_reached(&self, floor: i64) -> Option<u64> {
+		self.stats(floor).map(|stats| stats.first_reached)
+	}
+
+	/// How many steps ended on `floor`.
+	pub fn steps_on(&self, floor: i64) -> u64 {
+		self.stats(floor).map_or(0, |stats| stats.steps)
+	}
+
+	/// Every floor reached, from the lowest up.
+	pub fn floors(&self) -> impl Iterator<Item = (i64, FloorStats)> + '_ {
+		self.floors.iter().enumerate().map(move |(idx, &stats)| (self.lowest + idx as i64, stats))
+	}
+}
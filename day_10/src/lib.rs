@@ -0,0 +1,125 @@
+//! Day 10: Elves Look, Elves Say
+//!
+//! Repeated rounds of [`look_and_say`].
+
+extern crate aoc_common;
+
+use aoc_common::{Generator, ParseError, PuzzleInput, Rng, Solution};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day10.txt");
+
+/// One round of the game: reads out each run of digits as its length and the
+/// digit, so "111221" becomes "312211".
+pub fn look_and_say(input: &str) -> String {
+
+    let mut output = "".to_string();
+    let mut chr_count = 0;
+    let mut last_chr = ' ';
+    for chr in input.chars() {
+        if chr == last_chr {
+            chr_count += 1;
+        } else if chr_count > 0 {
+            let substr = format!("{}{}", chr_count, last_chr);
+            output.push_str(&substr);
+            chr_count = 1;
+        } else {
+            chr_count = 1;
+        }
+
+        last_chr = chr;
+    }
+
+    if chr_count > 0 {
+        let substr = format!("{}{}", chr_count, last_chr);
+        output.push_str(&substr);
+    }
+    // println!("{} => {}", input, output);
+    output
+}
+
+fn apply_rounds(input: &str, rounds: usize) -> String {
+    let mut stage = input.to_string();
+    for _ in 0..rounds {
+        stage = look_and_say(&stage);
+    }
+    stage
+}
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = String;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &PuzzleInput) -> Result<String, ParseError> {
+        let mut line = input.single_line()?;
+        let token = line.next("a sequence of digits")?;
+        if !token.text.chars().all(|c| c.is_ascii_digit()) {
+            return Err(line.error(token, "a sequence of digits"));
+        }
+        line.end()?;
+        Ok(token.text.to_string())
+    }
+
+    fn part1(input: &String) -> usize {
+        // println!("Final string: {}", stage);
+        apply_rounds(input, 40).len()
+    }
+
+    fn part2(input: &String) -> usize {
+        apply_rounds(input, 50).len()
+    }
+}
+
+// A starting sequence of `size` digits from 1 to 3, like the real inputs.
+impl Generator for Day10 {
+    const DEFAULT_SIZE: usize = 10;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size.max(1)).map(|_| (b'1' + rng.below(3) as u8) as char).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn look_and_say_examples() {
+        assert_eq!(look_and_say("1"), "11");
+        assert_eq!(look_and_say("11"), "21");
+        assert_eq!(look_and_say("21"), "1211");
+        assert_eq!(look_and_say("1211"), "111221");
+        assert_eq!(look_and_say("111221"), "312211");
+    }
+
+    #[test]
+    fn rounds_chain() {
+        assert_eq!(apply_rounds("1", 5), "312211");
+    }
+
+    // Reads a look-and-say sequence back: "1211" is one 2 then one 1, so "21".
+    fn say_back(sequence: &str) -> String {
+        let digits = sequence.as_bytes();
+        digits.chunks(2)
+              .map(|pair| ((pair[1] as char).to_string()).repeat((pair[0] - b'0') as usize))
+              .collect()
+    }
+
+    #[test]
+    fn generated_inputs_say_back() {
+        for seed in 0..20 {
+            let text = aoc_common::generate::<Day10>(seed, None);
+            let mut stage = Day10::parse(&PuzzleInput::from_text("generated", &text).unwrap()).unwrap();
+            assert_eq!(stage, text);
+            for _ in 0..10 {
+                let next = look_and_say(&stage);
+                assert_eq!(say_back(&next), stage);
+                stage = next;
+            }
+        }
+    }
+}
@@ -0,0 +1,241 @@
+//! Day 11: Corporate Policy
+//!
+//! Santa's next passwords; [`is_valid_password`] checks the rules and
+//! [`next_valid_password`] finds the next one.
+
+extern crate aoc_common;
+#[macro_use] extern crate log;
+
+use std::char;
+use std::error::Error;
+use std::fmt;
+
+use aoc_common::{Generator, ParseError, PuzzleInput, Rng, Solution};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day11.txt");
+
+// --------------------------------------------------------
+
+// Returns true on overflow
+fn increment_char(letter: &mut char) -> bool {
+	if *letter == 'z' {
+		*letter = 'a';
+		true
+	} else {
+		*letter = char::from_u32(*letter as u32 + 1).unwrap();
+		false
+	}
+}
+
+// --------------------------------------------------------
+
+/// There's no password after one that's all z's.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WrappedAround;
+
+impl fmt::Display for WrappedAround {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "no password comes after one that's all z's")
+	}
+}
+
+impl Error for WrappedAround {}
+
+/// Moves a password of lowercase letters on to the next one in place, like an
+/// odometer: "xz" becomes "ya". A password that's all z's is left as it is.
+pub fn increment_password(password: &mut [char]) -> Result<(), WrappedAround> {
+	if password.iter().all(|&letter| letter == 'z') {
+		return Err(WrappedAround);
+	}
+
+	let mut char_idx = password.len() - 1; // Last index
+	let mut needs_increment = true;
+	while needs_increment {
+		needs_increment = increment_char(&mut password[char_idx]);
+		if needs_increment {
+			needs_increment = true;
+			char_idx -= 1;
+		}
+	}
+	Ok(())
+}
+
+// --------------------------------------------------------
+
+/// Whether a password meets the corporate policy: no i, o or l, a straight of
+/// three letters like "abc", and two pairs like "aa" and "zz". A run of three
+/// or more of one letter only counts as one pair.
+pub fn is_valid_password(password: &[char]) -> bool {
+
+	let mut has_sequence = false;
+	let mut num_pairs = 0;
+
+	let mut back_two = 999u32;
+	let mut back_one = 999u32;
+	for c in password {
+		//print!("{} ", c);
+		if *c == 'i' || *c == 'o' || *c == 'l' {
+			return false;
+		}
+		
+		
+		if *c as u32 == back_one 
+		   && *c as u32 != back_two {
+			num_pairs += 1;
+		}
+		
+		if *c as u32 == back_one + 1 
+		   && back_one == back_two + 1 {
+			has_sequence = true;
+		}
+		
+		back_two = back_one;
+		back_one = *c as u32;
+	}
+	
+	has_sequence && num_pairs >= 2
+}
+
+// --------------------------------------------------------
+
+fn log_password(password: &[char]) {
+	debug!("{}", password.iter().collect::<String>());
+}
+
+/// Increments the password in place until it passes muster. A password that
+/// is already valid is left as it is. Fails if no valid password comes after.
+pub fn next_valid_password(password_chars: &mut [char]) -> Result<(), WrappedAround> {
+	while !is_valid_password(password_chars) {
+		increment_password(password_chars)?;
+		//log_password(&password_chars);
+	}
+	Ok(())
+}
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day11;
+
+impl Solution for Day11 {
+	type Input = String;
+	type Part1 = String;
+	type Part2 = String;
+
+	fn parse(input: &PuzzleInput) -> Result<String, ParseError> {
+		let mut line = input.single_line()?;
+		let token = line.next("the old password")?;
+		if token.text.len() != 8 || !token.text.chars().all(|c| c.is_ascii_lowercase()) {
+			return Err(line.error(token, "a password of eight lowercase letters"));
+		}
+		line.end()?;
+		Ok(token.text.to_string())
+	}
+
+	fn part1(old_password: &String) -> String {
+		let mut password_chars: Vec<char> = old_password.chars().collect();
+
+		log_password(&password_chars);
+
+		next_valid_password(&mut password_chars).expect("a valid password comes after the old one");
+
+		password_chars.into_iter().collect()
+	}
+
+	fn part2(old_password: &String) -> String {
+		let mut password_chars: Vec<char> = old_password.chars().collect();
+		// Part 2: run it again...
+		next_valid_password(&mut password_chars)
+			.and_then(|()| increment_password(&mut password_chars))
+			.and_then(|()| next_valid_password(&mut password_chars))
+			.expect("two valid passwords come after the old one");
+
+		password_chars.into_iter().collect()
+	}
+}
+
+// An old password of eight letters, skipping the forbidden i, o and l. It
+// starts no later than 'x' so there's always a next valid password.
+impl Generator for Day11 {
+	const DEFAULT_SIZE: usize = 8;
+
+	fn generate(rng: &mut Rng, _size: usize) -> String {
+		let letters: Vec<char> = ('a'..='z').filter(|c| !"iol".contains(*c)).collect();
+		let mut password = String::new();
+		password.push(*rng.choose(&letters[..letters.len() - 2]));
+		for _ in 1..8 {
+			password.push(*rng.choose(&letters));
+		}
+		password
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn chars(password: &str) -> Vec<char> {
+		password.chars().collect()
+	}
+
+	#[test]
+	fn validity_examples() {
+		assert!(!is_valid_password(&chars("hijklmmn")));
+		assert!(!is_valid_password(&chars("abbceffg")));
+		assert!(!is_valid_password(&chars("abbcegjk")));
+		assert!(is_valid_password(&chars("abcdffaa")));
+		assert!(is_valid_password(&chars("ghjaabcc")));
+		// A pair can repeat, but a run of one letter is only one pair
+		assert!(is_valid_password(&chars("aabaabcd")));
+		assert!(!is_valid_password(&chars("aaaabcdx")));
+	}
+
+	#[test]
+	fn increment_stops_at_the_last_password() {
+		let mut password = chars("xz");
+		assert_eq!(increment_password(&mut password), Ok(()));
+		assert_eq!(password, chars("ya"));
+
+		let mut password = chars("zzzzzzzz");
+		assert_eq!(increment_password(&mut password), Err(WrappedAround));
+		assert_eq!(password, chars("zzzzzzzz"));
+
+		let mut password = chars("zzzzzzzy");
+		assert_eq!(next_valid_password(&mut password), Err(WrappedAround));
+	}
+
+	#[test]
+	fn next_password_examples() {
+		assert_eq!(Day11::part1(&"abcdefgh".to_string()), "abcdffaa");
+		assert_eq!(Day11::part1(&"ghijklmn".to_string()), "ghjaabcc");
+	}
+
+	// The policy again, spelled out one rule at a time.
+	fn follows_policy(password: &str) -> bool {
+		let letters = password.as_bytes();
+		let straight = letters.windows(3).any(|run| run[1] == run[0] + 1 && run[2] == run[1] + 1);
+		let mut pairs = 0;
+		let mut idx = 1;
+		while idx < letters.len() {
+			if letters[idx] == letters[idx - 1] {
+				pairs += 1;
+				// Skip the rest of a run of this letter.
+				while idx < letters.len() && letters[idx] == letters[idx - 1] {
+					idx += 1;
+				}
+			}
+			idx += 1;
+		}
+		straight && pairs >= 2 && !password.contains(['i', 'o', 'l'])
+	}
+
+	#[test]
+	fn generated_passwords_follow_the_policy() {
+		for seed in 0..20 {
+			let text = aoc_common::generate::<Day11>(seed, None);
+			let old_password = Day11::parse(&PuzzleInput::from_text("generated", &text).unwrap()).unwrap();
+			let (first, second) = (Day11::part1(&old_password), Day11::part2(&old_password));
+			assert!(follows_policy(&first) && follows_policy(&second), "seed {}", seed);
+			assert!(old_password <= first && first < second, "seed {}", seed);
+		}
+	}
+}
@@ -0,0 +1,207 @@
+//! Day 12: JSAbacusFramework.io
+//!
+//! Summing the numbers in a JSON document, optionally skipping red objects.
+
+extern crate aoc_common;
+#[macro_use] extern crate log;
+
+extern crate rustc_serialize;
+use rustc_serialize::json::*;
+
+use aoc_common::{Generator, ParseError, PuzzleInput, Rng, Solution};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day12.txt");
+
+// --------------------------------------------------------
+
+// `label` is the object key leading to this node, for the trace output.
+fn parse_json_tree(json: &Json, tree_depth: usize, ignore_red: bool, label: &str) -> i64 {
+	
+	// Json types: I64, U64, F64, Boolean, String, Array, Object, Null
+	let node = |value: &dyn std::fmt::Display| trace!("{: >1$}{2}{3}", "", tree_depth, label, value);
+	match *json {
+		Json::Null 			=> { node(&"(null)"); 0},
+		Json::I64(v)     	=> { node(&v);  v},
+		Json::U64(v)     	=> { node(&v);  v as i64},
+		Json::F64(v)     	=> { node(&v);  v as i64},
+		Json::Boolean(b) 	=> { node(&b);  0},
+		Json::String(ref s) => { node(s);  0},
+		Json::Array(ref arr) => {
+			node(&"(array)");
+			let mut sum = 0;
+			for arr_obj in arr {
+				sum += parse_json_tree(arr_obj, tree_depth+1, ignore_red, "");
+			}
+			sum
+		},
+		Json::Object(ref obj) => {
+			node(&"(object)");
+			let mut sum = 0;
+			for (key, value) in obj.iter() {
+				sum += parse_json_tree(value, tree_depth+1, ignore_red, &format!("{}: ", key));
+				
+				// Skip counting this object if a key contains "red"
+				if let Json::String(ref s) = *value {
+					if ignore_red && s == "red" { return 0 }
+				}
+			}
+			sum
+		},
+	}
+}
+
+// --------------------------------------------------------
+
+// Points a JSON syntax error at the line and column the parser stopped at.
+fn json_error(input: &PuzzleInput, error: &ParserError) -> ParseError {
+	let (code, line_num, column) = match *error {
+		ParserError::SyntaxError(code, line_num, column) => (code, line_num, column),
+		ParserError::IoError(_) => (ErrorCode::EOFWhileParsingValue, usize::MAX, 0),
+	};
+
+	let line = input.tokens()
+		.nth(line_num.saturating_sub(1))
+		.unwrap_or_else(|| input.last_line());
+	let text = line.text();
+	let expected = format!("valid JSON ({})", error_str(code));
+	match text.char_indices().nth(column.saturating_sub(1)) {
+		Some((offset, chr)) => line.error_at(offset, 1, &expected, &format!("'{}'", chr)),
+		None => line.error_at_end(&expected),
+	}
+}
+
+// --------------------------------------------------------
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day12;
+
+impl Solution for Day12 {
+	type Input = Json;
+	type Part1 = i64;
+	type Part2 = i64;
+
+	fn parse(input: &PuzzleInput) -> Result<Json, ParseError> {
+		Json::from_str(input.text()).map_err(|error| json_error(input, &error))
+	}
+
+	fn part1(json: &Json) -> i64 {
+		parse_json_tree(json, 0, false, "")
+	}
+
+	fn part2(json: &Json) -> i64 {
+		parse_json_tree(json, 0, true, "")
+	}
+}
+
+const COLORS: [&str; 6] = ["red", "orange", "yellow", "green", "blue", "violet"];
+
+// Appends a random JSON value to `json`, using up some of `budget`, the
+// number of values left to write.
+fn write_json_value(rng: &mut Rng, depth: usize, budget: &mut usize, json: &mut String) {
+	*budget = budget.saturating_sub(1);
+	let can_nest = depth < 6 && *budget > 0;
+	match rng.below(if can_nest { 10 } else { 5 }) {
+		0..=2 => json.push_str(&rng.between(-50, 200).to_string()),
+		3 | 4 => json.push_str(&format!("\"{}\"", rng.choose(&COLORS))),
+		5..=7 => {
+			json.push('[');
+			for idx in 0..rng.between(1, 6) {
+				if *budget == 0 {
+					break;
+				}
+				if idx > 0 {
+					json.push(',');
+				}
+				write_json_value(rng, depth + 1, budget, json);
+			}
+			json.push(']');
+		}
+		_ => {
+			let mut keys: Vec<char> = ('a'..='j').collect();
+			rng.shuffle(&mut keys);
+			json.push('{');
+			for (idx, key) in keys.iter().take(rng.between(1, 6) as usize).enumerate() {
+				if *budget == 0 {
+					break;
+				}
+				if idx > 0 {
+					json.push(',');
+				}
+				json.push_str(&format!("\"{}\":", key));
+				write_json_value(rng, depth + 1, budget, json);
+			}
+			json.push('}');
+		}
+	}
+}
+
+// A single line of JSON with about `size` values: numbers, colors, and the
+// arrays and objects holding them.
+impl Generator for Day12 {
+	const DEFAULT_SIZE: usize = 2000;
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		let mut budget = size;
+		let mut json = String::from("[");
+		while budget > 0 {
+			if json.len() > 1 {
+				json.push(',');
+			}
+			write_json_value(rng, 1, &mut budget, &mut json);
+		}
+		json.push(']');
+		json
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn example(text: &str) -> Json {
+		Day12::parse(&PuzzleInput::from_text("example", text).unwrap()).unwrap()
+	}
+
+	#[test]
+	fn part1_examples() {
+		let cases = [
+			("[1,2,3]", 6), (r#"{"a":2,"b":4}"#, 6),
+			("[[[3]]]", 3), (r#"{"a":{"b":4},"c":-1}"#, 3),
+			(r#"{"a":[-1,1]}"#, 0), (r#"[-1,{"a":1}]"#, 0),
+			("[]", 0), ("{}", 0),
+		];
+		for &(text, sum) in &cases {
+			assert_eq!(Day12::part1(&example(text)), sum, "{}", text);
+		}
+	}
+
+	#[test]
+	fn part2_examples() {
+		let cases = [
+			("[1,2,3]", 6),
+			(r#"[1,{"c":"red","b":2},3]"#, 4),
+			(r#"{"d":"red","e":[1,2,3,4],"f":5}"#, 0),
+			(r#"[1,"red",5]"#, 6),
+		];
+		for &(text, sum) in &cases {
+			assert_eq!(Day12::part2(&example(text)), sum, "{}", text);
+		}
+	}
+
+	#[test]
+	fn generated_inputs_solve() {
+		for seed in 0..20 {
+			let text = aoc_common::generate::<Day12>(seed, Some(200));
+			let json = example(&text);
+
+			// Keys and strings are all letters, so part 1 is the sum of every number in the text.
+			let total: i64 = text.split(|c: char| c != '-' && !c.is_ascii_digit())
+								 .filter(|number| !number.is_empty())
+								 .map(|number| number.parse::<i64>().unwrap())
+								 .sum();
+			assert_eq!(Day12::part1(&json), total);
+			Day12::part2(&json);
+		}
+	}
+}
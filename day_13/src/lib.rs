@@ -0,0 +1,232 @@
+//! Day 13: Knights of the Dinner Table
+//!
+//! The happiest seating around a circular table.
+
+extern crate aoc_common;
+
+use std::collections::HashMap;
+use std::cmp;
+extern crate permutohedron;
+
+extern crate nalgebra as na;
+use na::DMatrix;
+
+use aoc_common::{Generator, ParseError, PuzzleInput, Rng, Solution};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day13.txt");
+
+// --------------------------------------------------------
+
+fn get_name_index<'a>(name_indices: &mut HashMap<&'a str, usize>, name: &'a str) -> usize {
+	let next_open_index = name_indices.len();
+	*name_indices.entry(name).or_insert(next_open_index)
+}
+
+// --------------------------------------------------------
+
+// Lines look like "Alice would gain 54 happiness units by sitting next to Bob."
+fn create_happiness_mapping(input: &PuzzleInput) -> Result<(HashMap<&str, usize>, DMatrix<i32>), ParseError> {
+
+	let mut name_indices: HashMap<&str, usize> = HashMap::new();
+	let mut changes: Vec<(usize, usize, i32)> = Vec::new();
+
+	for mut line in input.tokens() {
+		let person_name = line.next("a name")?.text;
+		line.literal("would")?;
+		let sign = line.next("'gain' or 'lose'")?;
+		let is_gain = match sign.text {
+			"gain" => true,
+			"lose" => false,
+			_ => return Err(line.error(sign, "'gain' or 'lose'")),
+		};
+		let happiness_value = line.number::<i32>("a number of happiness units")?;
+		for word in &["happiness", "units", "by", "sitting", "next", "to"] {
+			line.literal(word)?;
+		}
+		let neighbor = line.next("a name followed by '.'")?;
+		if !neighbor.text.ends_with('.') || neighbor.text.len() == 1 {
+			return Err(line.error(neighbor, "a name followed by '.'"));
+		}
+		let neighbor_name = neighbor.trim_end(&['.']).text;
+		line.end()?;
+		
+		let happiness_change = if is_gain { happiness_value } else { -happiness_value };
+		let person_idx = get_name_index(&mut name_indices, person_name);
+		let neighbor_idx = get_name_index(&mut name_indices, neighbor_name);
+
+		changes.push((person_idx, neighbor_idx, happiness_change));
+	};
+
+	// One spare row and column of zeros, for seating yourself in part 2
+	let size = name_indices.len() + 1;
+	let mut happiness: DMatrix<i32> = DMatrix::zeros(size, size);
+	for (person_idx, neighbor_idx, happiness_change) in changes {
+		happiness[(person_idx, neighbor_idx)] = happiness_change;
+	}
+
+	Ok((name_indices, happiness))
+}
+
+// --------------------------------------------------------
+
+fn score_arrangement(happiness: &DMatrix<i32>, arrangement: &[usize]) -> i32 {
+	let mut score_sum = 0;
+	for idx in arrangement {
+		let low_idx = if *idx == 0 { arrangement.len()-1 } else { *idx - 1 };
+		let high_idx = if *idx == arrangement.len()-1 { 0 } else { *idx + 1 };
+		
+		let person = arrangement[*idx];		
+		let low_neighbor = arrangement[low_idx];
+		let high_neighbor = arrangement[high_idx];
+		
+		let lower_score = happiness[(person, low_neighbor)];
+		let upper_score = happiness[(person, high_neighbor)];
+		score_sum += lower_score + upper_score;
+		
+		// println!("  [{}, {}, {}] = {} + {}", 
+			// low_neighbor, person, high_neighbor,
+			// lower_score, upper_score);
+	}
+	score_sum
+}
+
+// --------------------------------------------------------
+
+fn find_max_score(happiness: &DMatrix<i32>, num_people: usize) -> i32 {
+	let mut seating: Vec<usize> = (0..num_people).collect();
+
+	let seating_permutations = permutohedron::Heap::new(&mut seating);
+
+	let mut max_score = i32::MIN;
+	for arrangement in seating_permutations {
+		let score = score_arrangement(happiness, &arrangement);
+		max_score = cmp::max(max_score, score);
+	}
+
+	// Not clear why this won't compile
+	//let best_idx = seating_permutations.max_by_key(|ref item| score_arrangement(&happiness, &item));
+
+	max_score
+}
+
+// --------------------------------------------------------
+
+/// How much each guest likes sitting next to each other guest.
+pub struct Happiness {
+	num_people: usize,
+	happiness: DMatrix<i32>,
+}
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day13;
+
+impl Solution for Day13 {
+	type Input = Happiness;
+	type Part1 = i32;
+	type Part2 = i32;
+
+	fn parse(input: &PuzzleInput) -> Result<Happiness, ParseError> {
+		let (name_indices_map, happiness) = create_happiness_mapping(input)?;
+
+		Ok(Happiness { num_people: name_indices_map.len(), happiness })
+	}
+
+	fn part1(input: &Happiness) -> i32 {
+		find_max_score(&input.happiness, input.num_people)
+	}
+
+	fn part2(input: &Happiness) -> i32 {
+		// Now add yourself with a happiness change of 0.
+		// The happiness map has a spare row of zeros...so just pretend there is one more person...
+		find_max_score(&input.happiness, input.num_people+1)
+	}
+}
+
+const GUEST_NAMES: [&str; 12] = [
+	"Alice", "Bob", "Carol", "David", "Eric", "Frank", "George", "Mallory",
+	"Nadia", "Oscar", "Peggy", "Trent",
+];
+
+// How every one of `size` guests feels about sitting next to each other guest.
+// Part 2 tries every seating of `size` + 1 people, so keep `size` small.
+impl Generator for Day13 {
+	const DEFAULT_SIZE: usize = 8;
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		let names: Vec<String> = (0..size.max(2))
+			.map(|idx| GUEST_NAMES.get(idx).map_or_else(|| format!("Guest{}", idx + 1), |name| name.to_string()))
+			.collect();
+
+		let mut lines = Vec::new();
+		for person in &names {
+			for neighbor in names.iter().filter(|&neighbor| neighbor != person) {
+				let change = rng.between(-100, 100);
+				let verb = if change < 0 { "lose" } else { "gain" };
+				lines.push(format!("{} would {} {} happiness units by sitting next to {}.",
+								   person, verb, change.abs(), neighbor));
+			}
+		}
+		lines.join("\n")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn part1_example() {
+		let input = PuzzleInput::from_path(concat!(env!("CARGO_MANIFEST_DIR"), "/day13_test.txt")).unwrap();
+		assert_eq!(Day13::part1(&Day13::parse(&input).unwrap()), 330);
+	}
+
+	// The best total of every seating of `guests` around the table, reading how
+	// each guest feels straight from the text. Anyone not in it feels nothing.
+	fn best_seating(text: &str, guests: &mut Vec<String>) -> i32 {
+		let mut feelings = HashMap::new();
+		for line in text.lines() {
+			let words: Vec<&str> = line.trim_end_matches('.').split(' ').collect();
+			let change: i32 = words[3].parse().unwrap();
+			feelings.insert((words[0], words[10]), if words[2] == "lose" { -change } else { change });
+		}
+		let feels = |guest: &String, neighbor: &String| *feelings.get(&(guest.as_str(), neighbor.as_str())).unwrap_or(&0);
+
+		// Guest 0 stays put and the rest take every order after them.
+		fn seat(guests: &mut Vec<String>, first: usize, total: &dyn Fn(&[String]) -> i32) -> i32 {
+			if first == guests.len() {
+				return total(guests);
+			}
+			let mut best = i32::MIN;
+			for idx in first..guests.len() {
+				guests.swap(first, idx);
+				best = cmp::max(best, seat(guests, first + 1, total));
+				guests.swap(first, idx);
+			}
+			best
+		}
+		let total = |table: &[String]| {
+			(0..table.len())
+				.map(|idx| {
+					let neighbor = &table[(idx + 1) % table.len()];
+					feels(&table[idx], neighbor) + feels(neighbor, &table[idx])
+				})
+				.sum()
+		};
+		seat(guests, 1, &total)
+	}
+
+	#[test]
+	fn generated_inputs_match_every_seating() {
+		for seed in 0..10 {
+			let text = aoc_common::generate::<Day13>(seed, Some(5));
+			let happiness = Day13::parse(&PuzzleInput::from_text("generated", &text).unwrap()).unwrap();
+			assert_eq!(happiness.num_people, 5);
+
+			let mut guests: Vec<String> = GUEST_NAMES[..5].iter().map(|name| name.to_string()).collect();
+			assert_eq!(Day13::part1(&happiness), best_seating(&text, &mut guests));
+			guests.push(String::from("You"));
+			assert_eq!(Day13::part2(&happiness), best_seating(&text, &mut guests));
+		}
+	}
+}
@@ -0,0 +1,259 @@
+//! Day 14: Reindeer Olympics
+//!
+//! Racing reindeer by distance and by points.
+
+extern crate aoc_common;
+#[macro_use] extern crate log;
+
+use std::cmp;
+
+use aoc_common::{Generator, ParseError, PuzzleInput, Rng, Solution, Tokens};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day14.txt");
+
+/// A reindeer's speed in km/s, and how long it can fly before it has to rest.
+pub struct Raindeer {
+    name: String,
+    speed: u32,
+    flying_time: u32,
+    rest_time: u32,
+}
+
+// --------------------------------------------------------
+
+// Lines look like
+// "Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds."
+fn get_raindeer(input: &PuzzleInput) -> Result<Vec<Raindeer>, ParseError> {
+
+    let mut raindeer = Vec::new();
+
+    for mut line in input.tokens() {
+        let name = line.next("a name")?.text.to_string();
+        line.literal("can")?;
+        line.literal("fly")?;
+        let speed = line.number::<u32>("a speed")?;
+        line.literal("km/s")?;
+        line.literal("for")?;
+        let flying = line.next("a number of seconds")?;
+        let flying_time = line.parse::<u32>(flying, "a number of seconds")?;
+        if flying_time == 0 {
+            return Err(line.error(flying, "a positive number of seconds"));
+        }
+        for word in &["seconds,", "but", "then", "must", "rest", "for"] {
+            line.literal(word)?;
+        }
+        let rest_time = line.number::<u32>("a number of seconds")?;
+        line.literal("seconds.")?;
+        line.end()?;
+
+        let rd = Raindeer {
+            name,
+            speed,
+            flying_time,
+            rest_time,
+        };
+
+        raindeer.push(rd);
+    }
+
+    Ok(raindeer)
+}
+
+// --------------------------------------------------------
+
+/// The reindeer, and how long the race lasts.
+pub struct Race {
+    raindeer: Vec<Raindeer>,
+    total_seconds: u32,
+}
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Race;
+    type Part1 = u64;
+    type Part2 = u32;
+
+    // Part 2 scores the race's last second too.
+    const VERSION: u32 = 2;
+
+    // The race lasts 2503 seconds unless a "seconds" parameter says otherwise
+    // (the example in the puzzle text uses 1000).
+    fn parse(input: &PuzzleInput) -> Result<Race, ParseError> {
+        let total_seconds = match input.param("seconds") {
+            Some(value) => {
+                let mut param = Tokens::new("--param seconds", 1, value);
+                let seconds = param.number("a number of seconds")?;
+                param.end()?;
+                seconds
+            }
+            None => 2503,
+        };
+
+        Ok(Race {
+            raindeer: get_raindeer(input)?,
+            total_seconds,
+        })
+    }
+
+    fn part1(race: &Race) -> u64 {
+        best_distance(&race.raindeer, race.total_seconds)
+    }
+
+    fn part2(race: &Race) -> u32 {
+        best_score(&race.raindeer, race.total_seconds)
+    }
+}
+
+// Kilometres flown after `seconds`, as full cycles of flying and resting and
+// then what's left. Never more than `seconds` times the speed, so it fits in
+// a u64 whatever the input says.
+fn distance_after(rd: &Raindeer, seconds: u32) -> (u64, u64, u64) {
+    let cycle_time = rd.flying_time as u64 + rd.rest_time as u64;
+    let num_full_cycles = seconds as u64 / cycle_time;
+    let full_cycle_dist = num_full_cycles * rd.flying_time as u64 * rd.speed as u64;
+
+    let remaining_seconds = seconds as u64 % cycle_time;
+    let remaining_fly_time = cmp::min(remaining_seconds, rd.flying_time as u64);
+    let remaining_dist = remaining_fly_time * rd.speed as u64;
+
+    (num_full_cycles, full_cycle_dist, remaining_dist)
+}
+
+fn best_distance(raindeer: &[Raindeer], total_seconds: u32) -> u64 {
+
+    let mut best_distance = 0;
+    for rd in raindeer {
+        let (num_full_cycles, full_cycle_dist, remaining_dist) = distance_after(rd, total_seconds);
+        let total_dist = full_cycle_dist + remaining_dist;
+
+        debug!("{} went {} cycles covering {} km, then flew an extra {} km, for a total of \
+                {} km",
+               rd.name,
+               num_full_cycles,
+               full_cycle_dist,
+               remaining_dist,
+               total_dist);
+
+        best_distance = cmp::max(best_distance, total_dist);
+    }
+
+    best_distance
+}
+
+// Part 2: scoring by the second
+fn best_score(raindeer: &[Raindeer], total_seconds: u32) -> u32 {
+
+    let num_raindeer = raindeer.len();
+    let mut points = vec![0; num_raindeer];
+    for seconds in 1..=total_seconds {
+
+        let mut distances = vec![0; num_raindeer];
+        for (rd_idx, rd) in raindeer.iter().enumerate() {
+            let (_, full_cycle_dist, remaining_dist) = distance_after(rd, seconds);
+            distances[rd_idx] = full_cycle_dist + remaining_dist;
+        }
+
+        let best_distance = *distances.iter().max().unwrap();
+        for rd_idx in 0..num_raindeer {
+            if distances[rd_idx] == best_distance {
+                points[rd_idx] += 1;
+            }
+        }
+    }
+
+    *points.iter().max().unwrap()
+}
+
+const RAINDEER_NAMES: [&str; 9] = [
+    "Dancer", "Cupid", "Rudolph", "Donner", "Dasher", "Blitzen", "Prancer", "Comet", "Vixen",
+];
+
+// `size` reindeer with random speeds, flying times and rest times.
+impl Generator for Day14 {
+    const DEFAULT_SIZE: usize = 9;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let lines: Vec<String> = (0..size.max(1))
+            .map(|idx| {
+                let name = RAINDEER_NAMES.get(idx).map_or_else(|| format!("Reindeer{}", idx + 1), |name| name.to_string());
+                format!("{} can fly {} km/s for {} seconds, but then must rest for {} seconds.",
+                        name,
+                        rng.between(2, 25),
+                        rng.between(2, 20),
+                        rng.between(20, 180))
+            })
+            .collect();
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example race only lasts 1000 seconds.
+    fn example() -> Race {
+        let input = PuzzleInput::from_path(concat!(env!("CARGO_MANIFEST_DIR"), "/day14_test.txt")).unwrap();
+        Day14::parse(&input.with_param("seconds", "1000")).unwrap()
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day14::part1(&example()), 1120);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day14::part2(&example()), 689);
+    }
+
+    #[test]
+    fn fastest_reindeer_fit() {
+        let text = "Comet can fly 4294967295 km/s for 4294967295 seconds, but then must rest for 4294967295 seconds.";
+        let race = Day14::parse(&PuzzleInput::from_text("example", text).unwrap().with_param("seconds", "4294967295")).unwrap();
+        assert_eq!(Day14::part1(&race), 4294967295 * 4294967295);
+    }
+
+    // Flies every reindeer a second at a time, giving a point to the leaders
+    // after each second. Returns the furthest distance and the most points.
+    fn race_by_the_second(race: &Race) -> (u64, u32) {
+        let count = race.raindeer.len();
+        let (mut distances, mut points) = (vec![0u64; count], vec![0u32; count]);
+        let mut flying_left: Vec<u32> = race.raindeer.iter().map(|rd| rd.flying_time).collect();
+        let mut resting_left = vec![0u32; count];
+        for _ in 0..race.total_seconds {
+            for (idx, rd) in race.raindeer.iter().enumerate() {
+                if flying_left[idx] > 0 {
+                    distances[idx] += rd.speed as u64;
+                    flying_left[idx] -= 1;
+                    if flying_left[idx] == 0 {
+                        resting_left[idx] = rd.rest_time;
+                    }
+                } else {
+                    resting_left[idx] -= 1;
+                }
+                if flying_left[idx] == 0 && resting_left[idx] == 0 {
+                    flying_left[idx] = rd.flying_time;
+                }
+            }
+            let lead = *distances.iter().max().unwrap();
+            for idx in (0..count).filter(|&idx| distances[idx] == lead) {
+                points[idx] += 1;
+            }
+        }
+        (*distances.iter().max().unwrap(), *points.iter().max().unwrap())
+    }
+
+    #[test]
+    fn generated_races_match_the_second_by_second_race() {
+        for seed in 0..20 {
+            let text = aoc_common::generate::<Day14>(seed, Some(9));
+            let race = Day14::parse(&PuzzleInput::from_text("generated", &text).unwrap()).unwrap();
+            assert_eq!(race.raindeer.len(), 9);
+            assert_eq!((Day14::part1(&race), Day14::part2(&race)), race_by_the_second(&race), "seed {}", seed);
+        }
+    }
+}
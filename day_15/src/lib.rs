@@ -0,0 +1,354 @@
+//! Day 15: Science for Hungry People
+//!
+//! The best-scoring cookie recipe from a list of ingredients.
+
+extern crate aoc_common;
+#[macro_use] extern crate log;
+
+use std::cmp;
+use std::ops::RangeInclusive;
+
+use aoc_common::{Differential, Generator, ParseError, PuzzleInput, Rng, Solution};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day15.txt");
+
+// --------------------------------------------------------
+
+/// One line of the input: an ingredient's properties per teaspoon.
+#[derive(Debug)]
+#[allow(dead_code)]	// name is only kept for debug output
+pub struct Ingredient {
+	name: String,
+	capacity: i32,
+	durability: i32,
+	flavor: i32,
+	texture: i32,
+	calories: i32,
+}
+
+// --------------------------------------------------------
+
+// Lines look like
+// "Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8"
+fn get_ingredients_from_file(input: &PuzzleInput) -> Result<Vec<Ingredient>, ParseError> {
+	let properties = ["capacity", "durability", "flavor", "texture", "calories"];
+	
+	let mut ingredients: Vec<Ingredient> = Vec::new();
+	for mut line in input.tokens() {
+		let name = line.next("an ingredient name followed by ':'")?;
+		if !name.text.ends_with(':') || name.text.len() == 1 {
+			return Err(line.error(name, "an ingredient name followed by ':'"));
+		}
+		
+		let mut values = [0; 5];
+		for (idx, property) in properties.iter().enumerate() {
+			line.literal(property)?;
+			let value = line.next("a number")?;
+			// Every value but the last is followed by a comma
+			let is_last = idx == properties.len() - 1;
+			if value.text.ends_with(',') == is_last {
+				let expected = if is_last { "a number" } else { "a number followed by ','" };
+				return Err(line.error(value, expected));
+			}
+			values[idx] = line.parse::<i32>(value.trim_end(&[',']), "a number")?;
+		}
+		line.end()?;
+					   
+		let i = Ingredient { 
+			name:name.trim_end(&[':']).text.to_string(),
+			capacity:values[0], 
+			durability:values[1], 
+			flavor:values[2], 
+			texture:values[3], 
+			calories:values[4] };
+			
+		ingredients.push(i);		
+	}
+		
+    Ok(ingredients)
+}
+
+// --------------------------------------------------------
+
+fn get_score(ingredients: &[Ingredient], quantities: &[i32], calorie_target: Option<i32>) -> i32 {
+	let mut sub_scores = vec![0i32; 4];
+	let mut calories = 0;
+	for (ing, amount) in ingredients.iter().zip(quantities.iter()) {
+		sub_scores[0] += ing.capacity * amount;
+		sub_scores[1] += ing.durability * amount;
+		sub_scores[2] += ing.flavor * amount;
+		sub_scores[3] += ing.texture * amount;
+		calories += ing.calories * amount;
+	}
+
+	let raw_score = sub_scores.iter()
+			        .map(|&x| cmp::max(0, x))
+			        .product::<i32>();
+		
+	// Cookies that miss the calorie target don't count
+	let final_score = match calorie_target {
+		Some(target) if calories != target => 0,
+		_ => raw_score,
+	};
+	
+	trace!("Subscores are {:?} = {} with {} calories = {}", 
+		sub_scores, raw_score, calories, final_score);
+	
+	final_score
+}
+
+// --------------------------------------------------------
+
+// Capacity, durability, flavor, texture and calories.
+fn properties(ing: &Ingredient) -> [i32; 5] {
+	[ing.capacity, ing.durability, ing.flavor, ing.texture, ing.calories]
+}
+
+// Depth first over the amount of each ingredient, where the last one gets
+// whatever is left. A branch is skipped when even the best case (every
+// property as high as the teaspoons left could make it) can't beat the best
+// recipe found so far, or can't end up at `calorie_target`.
+fn search(ingredients: &[Ingredient], totals: [i32; 5], left: i32, calorie_target: Option<i32>, best: &mut i32) {
+	let add = |ing: &Ingredient, amount: i32| {
+		let mut totals = totals;
+		for (total, property) in totals.iter_mut().zip(properties(ing).iter()) {
+			*total += property * amount;
+		}
+		totals
+	};
+
+	let (first, rest) = match ingredients.split_first() {
+		Some(split) => split,
+		None => return,
+	};
+	if rest.is_empty() {
+		let totals = add(first, left);
+		if calorie_target.is_none_or(|target| totals[4] == target) {
+			let score = totals[..4].iter().map(|&total| cmp::max(0, total)).product::<i32>();
+			*best = cmp::max(*best, score);
+		}
+		return;
+	}
+
+	// Bounds are i64, since the best case can be far past any real score
+	let most = |property: usize| ingredients.iter().map(|ing| properties(ing)[property]).max().unwrap_or(0);
+	let bound = (0..4).map(|property| cmp::max(0, totals[property] + left * most(property)) as i64).product::<i64>();
+	if bound <= *best as i64 {
+		return;
+	}
+	if let Some(target) = calorie_target {
+		let fewest = ingredients.iter().map(|ing| ing.calories).min().unwrap_or(0);
+		if target < totals[4] + left * fewest || target > totals[4] + left * most(4) {
+			return;
+		}
+	}
+
+	for amount in 0..left + 1 {
+		search(rest, add(first, amount), left - amount, calorie_target, best);
+	}
+}
+
+// The best score of any recipe, tried exhaustively but skipping amounts that
+// can't beat the best so far or reach the calorie target.
+fn find_best_score(ingredients: &[Ingredient], calorie_target: Option<i32>) -> i32 {
+	let mut best = 0;
+	search(ingredients, [0; 5], 100, calorie_target, &mut best);
+	debug!("Best score is {}", best);
+	best
+}
+
+// --------------------------------------------------------
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day15;
+
+impl Solution for Day15 {
+	type Input = Vec<Ingredient>;
+	type Part1 = i32;
+	type Part2 = i32;
+
+	// Part 2 only counts cookies with exactly 500 calories.
+	const VERSION: u32 = 2;
+
+	fn parse(input: &PuzzleInput) -> Result<Vec<Ingredient>, ParseError> {
+		get_ingredients_from_file(input)
+	}
+
+	fn part1(ingredients: &Vec<Ingredient>) -> i32 {
+		find_best_score(ingredients, None)
+	}
+
+	// Part 2: only cookies with exactly 500 calories count.
+	fn part2(ingredients: &Vec<Ingredient>) -> i32 {
+		find_best_score(ingredients, Some(500))
+	}
+}
+
+// Scores every way of splitting the 100 teaspoons. Slow past a few ingredients.
+fn exhaustive_best_score(ingredients: &[Ingredient], calorie_target: Option<i32>) -> i32 {
+	fn best(ingredients: &[Ingredient], quantities: &mut Vec<i32>, left: i32, calorie_target: Option<i32>) -> i32 {
+		if quantities.len() + 1 == ingredients.len() {
+			quantities.push(left);
+			let score = get_score(ingredients, quantities, calorie_target);
+			quantities.pop();
+			return score;
+		}
+
+		(0..left + 1)
+			.map(|amount| {
+				quantities.push(amount);
+				let score = best(ingredients, quantities, left - amount, calorie_target);
+				quantities.pop();
+				score
+			})
+			.max()
+			.unwrap_or(0)
+	}
+
+	best(ingredients, &mut Vec::new(), 100, calorie_target)
+}
+
+const INGREDIENT_NAMES: [&str; 8] = [
+	"Sprinkles", "Butterscotch", "Chocolate", "Candy", "Frosting", "Sugar", "Cinnamon", "Vanilla",
+];
+
+// Quantities adding up to 100 teaspoons.
+fn sample_quantities(rng: &mut Rng, count: usize) -> Vec<i32> {
+	let mut quantities = Vec::with_capacity(count);
+	let mut sum = 0;
+	for _ in 1..count {
+		let quantity = rng.between(0, 100 - sum);
+		quantities.push(quantity);
+		sum += quantity;
+	}
+	quantities.push(100 - sum);
+	quantities
+}
+
+fn random_ingredients(rng: &mut Rng, count: usize) -> Vec<Ingredient> {
+	(0..count)
+		.map(|idx| {
+			// Like the real inputs, each ingredient is good for one property (two
+			// when there are fewer than four ingredients, or more than four) and
+			// bad for one other. Every property has an ingredient good for it.
+			let mut good: Vec<usize> = (0..4).filter(|property| property % count == idx).collect();
+			if good.is_empty() {
+				good.push(rng.below(4));
+			}
+			if idx >= 4 {
+				good.push((good[0] + 1 + rng.below(3)) % 4);
+			}
+			// At most 8 good points per teaspoon keeps every score within an i32.
+			let most = if good.len() > 1 { 4 } else { 5 };
+
+			let mut properties = [0; 4];
+			for &property in &good {
+				properties[property] = rng.between(2, most);
+			}
+			let bad: Vec<usize> = (0..4).filter(|property| !good.contains(property)).collect();
+			if !bad.is_empty() {
+				properties[*rng.choose(&bad)] = rng.between(-3, -1);
+			}
+
+			Ingredient {
+				name: INGREDIENT_NAMES.get(idx).map_or_else(|| format!("Ingredient{}", idx + 1), |name| name.to_string()),
+				capacity: properties[0],
+				durability: properties[1],
+				flavor: properties[2],
+				texture: properties[3],
+				calories: rng.between(1, 8),
+			}
+		})
+		.collect()
+}
+
+// `size` ingredients. Only sets where plenty of random quantities score (with
+// and without the 500 calorie target) are kept, so both parts have an answer.
+impl Generator for Day15 {
+	const DEFAULT_SIZE: usize = 4;
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		let count = size.max(2);
+		let ingredients = loop {
+			let ingredients = random_ingredients(rng, count);
+			let mut scoring = 0;
+			let mut scoring_at_500 = 0;
+			for _ in 0..1000 {
+				let quantities = sample_quantities(rng, count);
+				let calories: i32 = ingredients.iter().zip(&quantities).map(|(ing, amount)| ing.calories * amount).sum();
+				if get_score(&ingredients, &quantities, None) > 0 {
+					scoring += 1;
+					if calories == 500 {
+						scoring_at_500 += 1;
+					}
+				}
+			}
+			if scoring >= 10 && scoring_at_500 >= 1 {
+				break ingredients;
+			}
+		};
+
+		let lines: Vec<String> = ingredients.iter()
+			.map(|ing| format!("{}: capacity {}, durability {}, flavor {}, texture {}, calories {}",
+							   ing.name, ing.capacity, ing.durability, ing.flavor, ing.texture, ing.calories))
+			.collect();
+		lines.join("\n")
+	}
+}
+
+// The pruned search against trying every recipe.
+impl Differential for Day15 {
+	type Answer = (i32, i32);
+
+	const SIZES: RangeInclusive<usize> = 2..=4;
+
+	fn reference(ingredients: &Vec<Ingredient>) -> (i32, i32) {
+		(exhaustive_best_score(ingredients, None), exhaustive_best_score(ingredients, Some(500)))
+	}
+
+	fn fast(ingredients: &Vec<Ingredient>) -> (i32, i32) {
+		(Day15::part1(ingredients), Day15::part2(ingredients))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn example() -> Vec<Ingredient> {
+		let input = PuzzleInput::from_path(concat!(env!("CARGO_MANIFEST_DIR"), "/day15_test.txt")).unwrap();
+		Day15::parse(&input).unwrap()
+	}
+
+	#[test]
+	fn part1_example() {
+		assert_eq!(get_score(&example(), &[44, 56], None), 62842880);
+		assert_eq!(Day15::part1(&example()), 62842880);
+	}
+
+	#[test]
+	fn part2_example() {
+		assert_eq!(get_score(&example(), &[40, 60], Some(500)), 57600000);
+		assert_eq!(Day15::part2(&example()), 57600000);
+	}
+
+	#[test]
+	fn part2_needs_exactly_500_calories() {
+		// The best recipe is 55 Sprinkles at 490 calories, but only 50 make 500
+		let text = "Sprinkles: capacity 2, durability -1, flavor 3, texture 0, calories 4\n\
+		            Butterscotch: capacity -1, durability 4, flavor 0, texture 2, calories 6";
+		let ingredients = Day15::parse(&PuzzleInput::from_text("example", text).unwrap()).unwrap();
+		assert_eq!(get_score(&ingredients, &[50, 50], Some(500)), 112500000);
+		assert_eq!(get_score(&ingredients, &[55, 45], Some(500)), 0);
+		assert!(get_score(&ingredients, &[55, 45], None) > 112500000);
+		assert_eq!(Day15::part2(&ingredients), 112500000);
+	}
+
+	#[test]
+	fn pruned_search_matches_exhaustive() {
+		if let Err(mismatch) = aoc_common::differential::check::<Day15>(0..10) {
+			panic!("{}", mismatch);
+		}
+	}
+}
@@ -0,0 +1,278 @@
+//! Day 16: Aunt Sue
+//!
+//! Finding the Sue whose remembered things match the MFCSAM readings.
+
+extern crate aoc_common;
+#[macro_use] extern crate log;
+
+use std::collections::HashMap;
+
+use aoc_common::{Generator, ParseError, PuzzleInput, Rng, Solution, Tokens};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day16.txt");
+
+/// The things remembered about each Sue, in input order.
+pub type Sues = Vec<Vec<(String, i32)>>;
+
+enum Ineq {
+	Equals(i32),
+	GreaterThan(i32),
+	LessThan(i32),
+}
+
+// --------------------------------------------------------
+
+// Lines look like "Sue 1: goldfish: 9, cars: 0, samoyeds: 9"
+fn read_sue(line: &mut Tokens) -> Result<Vec<(String, i32)>, ParseError> {
+	line.literal("Sue")?;
+	let number = line.next("a number followed by ':'")?;
+	if !number.text.ends_with(':') {
+		return Err(line.error(number, "a number followed by ':'"));
+	}
+	line.parse::<u32>(number.trim_end(&[':']), "a number followed by ':'")?;
+
+	let mut things = Vec::new();
+	loop {
+		let key = line.next("a thing followed by ':'")?;
+		if !key.text.ends_with(':') || key.text.len() == 1 {
+			return Err(line.error(key, "a thing followed by ':'"));
+		}
+		let value = line.next("a count")?;
+		let count = line.parse::<i32>(value.trim_end(&[',']), "a count")?;
+		things.push((key.trim_end(&[':']).text.to_string(), count));
+
+		// A comma means there's another thing to come
+		if !value.text.ends_with(',') {
+			break;
+		}
+	}
+	line.end()?;
+
+	Ok(things)
+}
+
+fn read_sues(input: &PuzzleInput) -> Result<Sues, ParseError> {
+	input.tokens().map(|mut line| read_sue(&mut line)).collect()
+}
+
+// True if any of the things remembered about a Sue rules her out.
+fn has_conflict(constraints: &HashMap<&str, Ineq>, things: &[(String, i32)]) -> bool {
+	things.iter().any(|&(ref key, value)| {
+		match constraints.get(key.as_str()) {
+			Some(&Ineq::Equals(present_value)) => value != present_value,
+			Some(&Ineq::GreaterThan(present_value)) => value <= present_value,
+			Some(&Ineq::LessThan(present_value)) => value >= present_value,
+			None => false,
+		}
+	})
+}
+
+fn find_sue (constraints: &HashMap<&str, Ineq>, sues: &Sues) -> i32 {
+
+	let mut sue_num = 0;
+	let mut sue_no_conflict = -1i32;
+	for things in sues {
+		sue_num += 1;
+		
+		if !has_conflict(constraints, things) {
+			debug!("Sue {} has no conflicts", sue_num);
+			sue_no_conflict = sue_num;
+		}
+	}
+	sue_no_conflict
+}
+
+
+// --------------------------------------------------------
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day16;
+
+impl Solution for Day16 {
+	type Input = Sues;
+	type Part1 = i32;
+	type Part2 = i32;
+
+	fn parse(input: &PuzzleInput) -> Result<Sues, ParseError> {
+		read_sues(input)
+	}
+
+	fn part1(sues: &Sues) -> i32 {
+		part1(sues)
+	}
+
+	fn part2(sues: &Sues) -> i32 {
+		part2(sues)
+	}
+}
+
+// The MFCSAM readings, taken as exact counts.
+fn exact_readings() -> HashMap<&'static str, Ineq> {
+	let mut sue_stats_exact = HashMap::new();
+	sue_stats_exact.insert("children", Ineq::Equals(3) );
+	sue_stats_exact.insert("cats", Ineq::Equals(7) );
+	sue_stats_exact.insert("samoyeds", Ineq::Equals(2) );
+	sue_stats_exact.insert("pomeranians", Ineq::Equals(3) );
+	sue_stats_exact.insert("akitas", Ineq::Equals(0) );
+	sue_stats_exact.insert("vizslas", Ineq::Equals(0) );
+	sue_stats_exact.insert("goldfish", Ineq::Equals(5) );
+	sue_stats_exact.insert("trees", Ineq::Equals(3) );
+	sue_stats_exact.insert("cars", Ineq::Equals(2) );
+	sue_stats_exact.insert("perfumes", Ineq::Equals(1) );
+	sue_stats_exact
+}
+
+fn part1(sues: &Sues) -> i32 {
+	debug!("Running part 1...");
+	find_sue(&exact_readings(), sues)
+}
+
+// The readings again, with cats and trees as lower bounds and pomeranians and
+// goldfish as upper bounds.
+fn ranged_readings() -> HashMap<&'static str, Ineq> {
+	let mut sue_stats_ineq = HashMap::new();
+	sue_stats_ineq.insert("children", Ineq::Equals(3) );
+	sue_stats_ineq.insert("cats", Ineq::GreaterThan(7) );
+	sue_stats_ineq.insert("samoyeds", Ineq::Equals(2) );
+	sue_stats_ineq.insert("pomeranians", Ineq::LessThan(3) );
+	sue_stats_ineq.insert("akitas", Ineq::Equals(0) );
+	sue_stats_ineq.insert("vizslas", Ineq::Equals(0) );
+	sue_stats_ineq.insert("goldfish", Ineq::LessThan(5) );
+	sue_stats_ineq.insert("trees", Ineq::GreaterThan(3) );
+	sue_stats_ineq.insert("cars", Ineq::Equals(2) );
+	sue_stats_ineq.insert("perfumes", Ineq::Equals(1) );
+	sue_stats_ineq
+}
+
+fn part2(sues: &Sues) -> i32 {
+	debug!("Running part 2...");
+	find_sue(&ranged_readings(), sues)
+}
+
+const THINGS: [&str; 10] = [
+	"children", "cats", "samoyeds", "pomeranians", "akitas",
+	"vizslas", "goldfish", "trees", "cars", "perfumes",
+];
+
+// Three different things remembered about a Sue, each a count up to 10.
+fn random_sue(rng: &mut Rng) -> Vec<(String, i32)> {
+	let mut things = THINGS;
+	rng.shuffle(&mut things);
+	things[..3].iter().map(|thing| (thing.to_string(), rng.between(0, 10))).collect()
+}
+
+// A random Sue that matches `matching` but not `conflicting`.
+fn random_matching_sue(rng: &mut Rng,
+					   matching: &HashMap<&str, Ineq>,
+					   conflicting: &HashMap<&str, Ineq>)
+					   -> Vec<(String, i32)> {
+	loop {
+		let sue = random_sue(rng);
+		if !has_conflict(matching, &sue) && has_conflict(conflicting, &sue) {
+			return sue;
+		}
+	}
+}
+
+// `size` Sues. Exactly one matches the exact readings, and exactly one
+// other matches the ranged readings.
+impl Generator for Day16 {
+	const DEFAULT_SIZE: usize = 500;
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		let size = size.max(2);
+		let exact = exact_readings();
+		let ranged = ranged_readings();
+
+		let mut sues: Vec<Vec<(String, i32)>> = (0..size)
+			.map(|_| loop {
+				let sue = random_sue(rng);
+				if has_conflict(&exact, &sue) && has_conflict(&ranged, &sue) {
+					break sue;
+				}
+			})
+			.collect();
+
+		let exact_idx = rng.below(size);
+		let ranged_idx = (exact_idx + 1 + rng.below(size - 1)) % size;
+		sues[exact_idx] = random_matching_sue(rng, &exact, &ranged);
+		sues[ranged_idx] = random_matching_sue(rng, &ranged, &exact);
+
+		let lines: Vec<String> = sues.iter()
+			.enumerate()
+			.map(|(idx, things)| {
+				let things: Vec<String> = things.iter().map(|&(ref thing, count)| format!("{}: {}", thing, count)).collect();
+				format!("Sue {}: {}", idx + 1, things.join(", "))
+			})
+			.collect();
+		lines.join("\n")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// The puzzle has no examples. Sue 2 only matches the exact readings and
+	// Sue 3 only matches the ranges.
+	const SUES: &str = "Sue 1: children: 1, cars: 8, vizslas: 7\n\
+	                    Sue 2: cats: 7, trees: 3, akitas: 0\n\
+	                    Sue 3: cats: 8, trees: 4, goldfish: 2";
+
+	fn sues() -> Sues {
+		Day16::parse(&PuzzleInput::from_text("example", SUES).unwrap()).unwrap()
+	}
+
+	#[test]
+	fn exact_readings() {
+		assert_eq!(Day16::part1(&sues()), 2);
+	}
+
+	#[test]
+	fn ranged_readings() {
+		assert_eq!(Day16::part2(&sues()), 3);
+	}
+
+	// Whether a Sue's line fits the MFCSAM readings, with or without the
+	// ranges, checked straight from the text.
+	fn fits(line: &str, ranged: bool) -> bool {
+		let (_, things) = line.split_once(": ").unwrap();
+		things.split(", ").all(|thing| {
+			let (name, count) = thing.split_once(": ").unwrap();
+			let count: i32 = count.parse().unwrap();
+			match (name, ranged) {
+				("cats", true) => count > 7,
+				("trees", true) => count > 3,
+				("pomeranians", true) => count < 3,
+				("goldfish", true) => count < 5,
+				("children", _) | ("pomeranians", _) | ("trees", _) => count == 3,
+				("cats", _) => count == 7,
+				("samoyeds", _) | ("cars", _) => count == 2,
+				("akitas", _) | ("vizslas", _) => count == 0,
+				("goldfish", _) => count == 5,
+				("perfumes", _) => count == 1,
+				_ => panic!("unknown thing {}", name),
+			}
+		})
+	}
+
+	#[test]
+	fn generated_inputs_have_one_sue_each() {
+		for seed in 0..20 {
+			let text = aoc_common::generate::<Day16>(seed, Some(50));
+			let sues = Day16::parse(&PuzzleInput::from_text("generated", &text).unwrap()).unwrap();
+			assert_eq!(sues.len(), 50);
+
+			for ranged in [false, true] {
+				let matching: Vec<i32> = text.lines()
+					.enumerate()
+					.filter(|&(_, line)| fits(line, ranged))
+					.map(|(idx, _)| idx as i32 + 1)
+					.collect();
+				let found = if ranged { Day16::part2(&sues) } else { Day16::part1(&sues) };
+				assert_eq!(matching, [found], "seed {}", seed);
+			}
+		}
+	}
+}
@@ -0,0 +1,127 @@
+//! Day 17: No Such Thing as Too Much
+//!
+//! Combinations of containers that hold exactly 150 liters of eggnog.
+
+extern crate aoc_common;
+#[macro_use] extern crate log;
+
+use aoc_common::{Generator, ParseError, PuzzleInput, Rng, Solution};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day17.txt");
+
+
+/// Number of permutations summing to value N
+/// Values: container sizes
+/// target: remaining eggnog to be stored
+/// prior_used: containers used prior to this call
+/// hist: histogram of container counts
+pub fn find_permutation_sums(values: &[i32], target: i32, prior_used: usize, hist: &mut [i32]) {
+
+	//let indent = 21-values.len();
+	//print!("{spacer:>0width$}", spacer=" ", width=indent);
+	//println!("{:?} : {}", values, target);
+
+	for i in 0..values.len() {
+		let remaining_target = target - values[i];
+		if remaining_target < 0 {
+			// Current value is too big
+			continue;
+		} else if remaining_target == 0 {
+			// Number is just the right size. Don't check extra values.
+			hist[prior_used + 1] += 1; // Record number of containers used
+			continue;
+		} else if i < values.len() - 1 {
+			// Check this value plus the sum from remaining values
+			let subslice = &values[i+1..];
+			find_permutation_sums(subslice, remaining_target, prior_used + 1, hist);
+		}		
+	}
+}
+
+
+// One container size per line
+fn read_containers(input: &PuzzleInput) -> Result<Vec<i32>, ParseError> {
+
+	let mut values: Vec<i32> = input.tokens()
+							  .map(|mut line| {
+								  let size = line.number::<i32>("a container size")?;
+								  line.end()?;
+								  Ok(size)
+							  })
+							  .collect::<Result<_, ParseError>>()?;
+
+	values.sort_by(|a, b| b.cmp(a));	// Reverse sort
+	debug!("Input values: {:?}", values);
+	Ok(values)
+}
+
+// Ways to store `liters` of eggnog, indexed by the number of containers used
+fn container_histogram(values: &[i32], liters: i32) -> Vec<i32> {
+	let mut histogram = vec![0; values.len() + 1];
+	find_permutation_sums(values, liters, 0, &mut histogram);
+	debug!("Histogram {:?}", histogram);
+	histogram
+}
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day17;
+
+impl Solution for Day17 {
+	type Input = Vec<i32>;
+	type Part1 = i32;
+	type Part2 = i32;
+
+	fn parse(input: &PuzzleInput) -> Result<Vec<i32>, ParseError> {
+		read_containers(input)
+	}
+
+	fn part1(values: &Vec<i32>) -> i32 {
+		container_histogram(values, 150).iter().sum()
+	}
+
+	// Number of ways to store the eggnog using the fewest possible containers
+	fn part2(values: &Vec<i32>) -> i32 {
+		container_histogram(values, 150).into_iter().find(|&count| count > 0).unwrap_or(0)
+	}
+}
+
+// `size` containers of 5 to 50 liters.
+impl Generator for Day17 {
+	const DEFAULT_SIZE: usize = 20;
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		let sizes: Vec<String> = (0..size).map(|_| rng.between(5, 50).to_string()).collect();
+		sizes.join("\n")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// 25 liters into containers of 20, 15, 10, 5 and 5
+	#[test]
+	fn example() {
+		let histogram = container_histogram(&[20, 15, 10, 5, 5], 25);
+		assert_eq!(histogram.iter().sum::<i32>(), 4);
+		assert_eq!(histogram.into_iter().find(|&count| count > 0), Some(3));
+	}
+
+	#[test]
+	fn generated_inputs_match_every_subset() {
+		for seed in 0..20 {
+			let text = aoc_common::generate::<Day17>(seed, Some(12));
+			let values = Day17::parse(&PuzzleInput::from_text("generated", &text).unwrap()).unwrap();
+
+			// Containers used by each subset that holds exactly 150 liters
+			let fits: Vec<u32> = (0u32..1 << values.len())
+				.filter(|subset| values.iter().enumerate().filter(|&(idx, _)| subset & 1 << idx != 0).map(|(_, liters)| liters).sum::<i32>() == 150)
+				.map(|subset| subset.count_ones())
+				.collect();
+			let fewest = fits.iter().min().copied();
+			assert_eq!(Day17::part1(&values), fits.len() as i32);
+			assert_eq!(Day17::part2(&values), fits.iter().filter(|&&used| Some(used) == fewest).count() as i32);
+		}
+	}
+}
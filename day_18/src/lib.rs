@@ -0,0 +1,203 @@
+//! Day 18: Like a GIF For Your Yard
+//!
+//! Conway's Life on a grid of lights; [`animate`] runs it a generation at a
+//! time.
+
+extern crate aoc_common;
+
+use aoc_common::{Generator, Grid, ParseError, Point, PuzzleInput, Rng, Solution};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day18.txt");
+
+/// A grid of '#' (on) and '.' (off).
+pub fn read_grid(input: &PuzzleInput) -> Result<Grid<bool>, ParseError> {
+	Grid::parse(input, "'#' or '.'", |c| match c {
+		'#' => Some(true),
+		'.' => Some(false),
+		_ => None,
+	})
+}
+
+// --------------------------------------------------------
+
+/// One generation of Life: a light stays on with two or three neighbours on,
+/// and turns on with exactly three.
+pub fn iterate(grid: &Grid<bool>) -> Grid<bool> {
+	let mut grid_out = grid.clone();
+	
+	for point in grid.points() {
+		let neighbor_sum = grid.neighbours(point).filter(|&neighbour| grid[neighbour]).count();
+		grid_out[point] = neighbor_sum == 3 || (neighbor_sum == 2 && grid[point]);
+	}
+	
+	grid_out
+}
+
+// --------------------------------------------------------
+
+/// How many lights are on.
+pub fn sum_grid(grid: &Grid<bool>) -> usize {
+	grid.iter().filter(|&&on| on).count()
+}
+
+// --------------------------------------------------------
+
+/// Turns the four corner lights on, which are stuck on in part 2.
+pub fn lock_corners(grid: &mut Grid<bool>) {
+	let max_x = grid.width() as i32 - 1;
+	let max_y = grid.height() as i32 - 1;
+	for &(x, y) in &[(0, 0), (max_x, 0), (0, max_y), (max_x, max_y)] {
+		grid[Point::new(x, y)] = true;
+	}
+}
+
+// --------------------------------------------------------
+
+/// Runs `steps` generations, calling `frame` with the starting grid and then
+/// after each step. With `stuck_corners`, the four corner lights are always on.
+pub fn animate(initial: &Grid<bool>, stuck_corners: bool, steps: usize, mut frame: impl FnMut(&Grid<bool>)) -> Grid<bool> {
+	let mut grid = initial.clone();
+	if stuck_corners {
+		lock_corners(&mut grid);
+	}
+	frame(&grid);
+
+	for _ in 0..steps {
+		grid = iterate(&grid);
+		if stuck_corners {
+			lock_corners(&mut grid);
+		}
+		frame(&grid);
+	}
+	grid
+}
+
+// --------------------------------------------------------
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day18;
+
+impl Solution for Day18 {
+	type Input = Grid<bool>;
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &PuzzleInput) -> Result<Grid<bool>, ParseError> {
+		read_grid(input)
+	}
+
+	fn part1(initial: &Grid<bool>) -> usize {
+		sum_grid(&animate(initial, false, 100, |_| {}))
+	}
+
+	fn part2(initial: &Grid<bool>) -> usize {
+		sum_grid(&animate(initial, true, 100, |_| {}))
+	}
+}
+
+// A `size` by `size` grid with about half the lights on.
+impl Generator for Day18 {
+	const DEFAULT_SIZE: usize = 100;
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		let rows: Vec<String> = (0..size.max(1))
+			.map(|_| (0..size.max(1)).map(|_| if rng.chance(0.5) { '#' } else { '.' }).collect())
+			.collect();
+		rows.join("\n")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn example(name: &str) -> Grid<bool> {
+		let input = PuzzleInput::from_path(&format!("{}/{}", env!("CARGO_MANIFEST_DIR"), name)).unwrap();
+		read_grid(&input).unwrap()
+	}
+
+	#[test]
+	fn part1_example() {
+		let mut grid = example("day18_test.txt");
+		let lights_on = [11, 8, 4, 4];
+		for &expected in &lights_on {
+			grid = iterate(&grid);
+			assert_eq!(sum_grid(&grid), expected);
+		}
+	}
+
+	#[test]
+	fn part2_example() {
+		let mut grid = example("day18_test2.txt");
+		let lights_on = [18, 18, 18, 14, 17];
+		for &expected in &lights_on {
+			grid = iterate(&grid);
+			lock_corners(&mut grid);
+			assert_eq!(sum_grid(&grid), expected);
+		}
+	}
+
+	#[test]
+	fn grids_can_be_rectangular() {
+		// A blinker, three lights wide, on a grid wider than it is tall
+		let input = PuzzleInput::from_text("example", ".....\n.###.\n.....").unwrap();
+		let grid = read_grid(&input).unwrap();
+		assert_eq!((grid.width(), grid.height()), (5, 3));
+
+		let mut counts = Vec::new();
+		animate(&grid, false, 2, |grid| counts.push(sum_grid(grid)));
+		assert_eq!(counts, [3, 3, 3]);
+		assert_eq!(iterate(&iterate(&grid)), grid);
+	}
+
+	#[test]
+	fn rows_must_match_the_first() {
+		let input = PuzzleInput::from_text("example", "#.#\n##\n").unwrap();
+		let error = read_grid(&input).unwrap_err();
+		assert_eq!((error.line, error.expected.as_str()), (2, "3 cells in a row"));
+	}
+
+	// Plays `steps` steps on rows of booleans, counting each light's
+	// neighbours one by one.
+	fn play(text: &str, stuck_corners: bool, steps: usize) -> usize {
+		let mut lights: Vec<Vec<bool>> = text.lines().map(|row| row.chars().map(|c| c == '#').collect()).collect();
+		let (height, width) = (lights.len(), lights[0].len());
+		let stick = |lights: &mut Vec<Vec<bool>>| {
+			if stuck_corners {
+				for &(y, x) in &[(0, 0), (0, width - 1), (height - 1, 0), (height - 1, width - 1)] {
+					lights[y][x] = true;
+				}
+			}
+		};
+
+		stick(&mut lights);
+		for _ in 0..steps {
+			let mut next = lights.clone();
+			for y in 0..height {
+				for x in 0..width {
+					// The 3x3 block around the light, less the light itself
+					let on = lights[y.saturating_sub(1)..(y + 2).min(height)]
+						.iter()
+						.map(|row| row[x.saturating_sub(1)..(x + 2).min(width)].iter().filter(|&&lit| lit).count())
+						.sum::<usize>() - lights[y][x] as usize;
+					next[y][x] = on == 3 || (lights[y][x] && on == 2);
+				}
+			}
+			lights = next;
+			stick(&mut lights);
+		}
+		lights.iter().flatten().filter(|&&on| on).count()
+	}
+
+	#[test]
+	fn generated_inputs_match_a_plain_game() {
+		for seed in 0..5 {
+			let text = aoc_common::generate::<Day18>(seed, Some(12));
+			let grid = read_grid(&PuzzleInput::from_text("generated", &text).unwrap()).unwrap();
+			assert_eq!((grid.width(), grid.height()), (12, 12));
+			assert_eq!(Day18::part1(&grid), play(&text, false, 100), "seed {}", seed);
+			assert_eq!(Day18::part2(&grid), play(&text, true, 100), "seed {}", seed);
+		}
+	}
+}
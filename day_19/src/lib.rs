@@ -0,0 +1,353 @@
+//! Day 19: Medicine for Rudolph
+//!
+//! Molecule replacements, and the fewest steps to make the medicine.
+
+extern crate aoc_common;
+
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+
+extern crate regex;
+use regex::Regex;
+
+use aoc_common::{Differential, Generator, ParseError, PuzzleInput, Rng, Solution};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day19.txt");
+
+/// A replacement rule, like "H => HO".
+pub struct Mapping {
+	input: String,
+	output: String,
+}
+
+// --------------------------------------------------------
+
+// Replacements like "Al => ThF", a blank line, then the medicine molecule.
+fn read_input(input: &PuzzleInput) -> Result<(Vec<Mapping>, String), ParseError> {
+	
+	let mut mapping_tuples = Vec::new();
+	let mut input_str: Option<String> = None;
+	
+	for mut line in input.tokens() {
+		let first = match line.next("") {
+			Ok(token) => token,
+			Err(_) => continue,	// blank separator line
+		};
+
+		// Molecules are plain ASCII, since they're sliced byte by byte later
+		if !first.text.chars().all(|c| c.is_ascii_alphabetic()) {
+			return Err(line.error(first, "an element or molecule"));
+		}
+		if input_str.is_some() {
+			return Err(line.error(first, "end of input"));
+		}
+
+		let arrow = match line.next("") {
+			Ok(token) => token,
+			Err(_) => {
+				input_str = Some(first.text.to_owned());
+				continue;
+			}
+		};
+		if arrow.text != "=>" {
+			return Err(line.error(arrow, "'=>'"));
+		}
+
+		// form A => B
+		let output = line.next("a replacement")?;
+		if !output.text.chars().all(|c| c.is_ascii_alphabetic()) {
+			return Err(line.error(output, "a replacement"));
+		}
+		line.end()?;
+		mapping_tuples.push( Mapping{input: first.text.to_string(), output: output.text.to_string()} );
+	}
+	
+	match input_str {
+		Some(input_str) => Ok((mapping_tuples, input_str)),
+		None => Err(input.last_line().error_at_end("a molecule after the replacements")),
+	}
+}
+
+// --------------------------------------------------------
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day19;
+
+impl Solution for Day19 {
+	type Input = (Vec<Mapping>, String);
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &PuzzleInput) -> Result<(Vec<Mapping>, String), ParseError> {
+		read_input(input)
+	}
+
+	fn part1(input: &(Vec<Mapping>, String)) -> usize {
+		let (ref mapping_list, ref molecule) = *input;
+		count_replacements(mapping_list, molecule)
+	}
+
+	fn part2(input: &(Vec<Mapping>, String)) -> usize {
+		count_reduction_steps(&input.1)
+	}
+}
+
+fn count_replacements(mapping_list: &[Mapping], input: &str) -> usize {
+	
+	let mut output_set = HashSet::new();
+	
+	let mut previous_c = '.';
+	for (idx, c) in input.chars().enumerate() {
+	
+		let chr = c.to_string();
+		let mut pair = previous_c.to_string();
+		pair.push(c);
+		//println!("Testing {} and {}", pair, chr);
+		
+		// Loop to check replacement rules
+		for mapping in mapping_list {
+		
+			// Note: string slicing is not safe for multi-byte characters	
+			if chr == mapping.input || pair == mapping.input {
+			
+				let post_bytes = &input[idx+1..];				
+				let replacement = &mapping.output;
+			
+				// 'idx' if replacing one character, idx-1 if replacing two.
+				let prior_idx = idx +1 - mapping.input.len();
+				let prior_bytes = &input[0..prior_idx];
+			
+				let result = format!("{}{}{}", &prior_bytes, &replacement, &post_bytes);
+				output_set.insert(result);
+			}
+		}
+		
+		previous_c = c;
+	}
+	
+	output_set.len()
+}
+
+fn count_reduction_steps(input: &str) -> usize {
+
+	// Part 2: Reduce the string to else
+	// Notes
+	//  * The rules are in the form
+	//       e => XX
+	//		 X => XX
+	//		 X => X Rn X Ar | X Rn X Y X Ar | X Rn X Y X Y Ar
+	//  * Thinking of Rn and Ar as () and Y as , we get
+	//       e => XX
+	//		 X => XX
+	//		 X => X(X) | X(X,X) || X(X,X,X)
+	// To reduce a string of normal tokens to a single token takes tokens.len() - 1
+	// To reduce a string of X(X) tokens takes tokens.len() - perens.len() - 1
+	// To reduce a string of X(X,X,X) is the same as above, but an extra two for each the ,X
+	
+	let mut working = input.replace("Rn", "(");
+	working = working.replace("Ar", ")");
+	working = working.replace("Y", ",");
+	//println!("Working str: {}", &working);
+	
+	// Convert double-character tokens to 'X's for counting
+	let re = Regex::new(r"[:upper:]{1}[:lower:]{1}").unwrap();
+	working = re.replace_all(&working, "X");
+
+	//println!("Working str: {}", &working);
+	
+	let token_count = working.len(); // FIXME: this needs to count tokens, not characters.
+	let peren_count = working.matches("(").count() + working.matches(")").count();
+	let sep_count = working.matches(",").count();
+	
+	token_count - peren_count - 2*sep_count - 1
+}
+
+// The molecule split into elements: a capital letter and any lowercase ones
+// after it, so "CaRn" is ["Ca", "Rn"].
+fn elements(molecule: &str) -> Vec<&str> {
+	let starts: Vec<usize> = molecule.char_indices()
+		.filter(|&(idx, c)| idx == 0 || c.is_ascii_uppercase())
+		.map(|(idx, _)| idx)
+		.collect();
+	starts.iter()
+		.enumerate()
+		.map(|(n, &start)| &molecule[start..starts.get(n + 1).cloned().unwrap_or(molecule.len())])
+		.collect()
+}
+
+// Part 1 the long way: replace each element in turn and collect the results.
+fn replace_each_element(mapping_list: &[Mapping], molecule: &str) -> usize {
+	let elements = elements(molecule);
+	let mut results = HashSet::new();
+	for (idx, &element) in elements.iter().enumerate() {
+		for mapping in mapping_list.iter().filter(|mapping| mapping.input == element) {
+			let result = format!("{}{}{}", elements[..idx].concat(), mapping.output, elements[idx + 1..].concat());
+			results.insert(result);
+		}
+	}
+	results.len()
+}
+
+// Part 2 the long way: a breadth-first search back from the molecule, undoing
+// one replacement per step until only "e" is left. Only usable on small
+// molecules. None if the molecule can't be made at all.
+fn search_reduction_steps(mapping_list: &[Mapping], molecule: &str) -> Option<usize> {
+	let mut seen = HashSet::new();
+	let mut frontier = vec![molecule.to_string()];
+	let mut steps = 0;
+	while !frontier.is_empty() {
+		let mut next = Vec::new();
+		for current in frontier {
+			if current == "e" {
+				return Some(steps);
+			}
+
+			for mapping in mapping_list {
+				for (idx, _) in current.match_indices(&mapping.output) {
+					let end = idx + mapping.output.len();
+					// Only whole elements: "C" isn't the start of "Ca"
+					if current[end..].starts_with(|c: char| c.is_ascii_lowercase()) {
+						continue;
+					}
+					// "e" can only be the whole molecule
+					if mapping.input == "e" && current.len() != mapping.output.len() {
+						continue;
+					}
+
+					let reduced = format!("{}{}{}", &current[..idx], mapping.input, &current[end..]);
+					if seen.insert(reduced.clone()) {
+						next.push(reduced);
+					}
+				}
+			}
+		}
+		frontier = next;
+		steps += 1;
+	}
+	None
+}
+
+// Elements with replacement rules. Like the real puzzle, C, Rn, Y and Ar only
+// ever appear in replacements.
+const ELEMENTS: [&str; 12] = ["Al", "B", "Ca", "F", "H", "Mg", "N", "O", "P", "Si", "Th", "Ti"];
+
+// A replacement in one of the puzzle's shapes: XX, X Rn X Ar, X Rn X Y X Ar
+// or X Rn X Y X Y Ar.
+fn random_replacement(rng: &mut Rng) -> Vec<&'static str> {
+	let mut output = vec![*rng.choose(&ELEMENTS)];
+	match rng.below(10) {
+		0..=6 => output.push(*rng.choose(&ELEMENTS)),
+		branches => {
+			if rng.chance(0.3) {
+				output[0] = "C";
+			}
+			output.push("Rn");
+			output.push(*rng.choose(&ELEMENTS));
+			for _ in 7..branches {
+				output.push("Y");
+				output.push(*rng.choose(&ELEMENTS));
+			}
+			output.push("Ar");
+		}
+	}
+	output
+}
+
+// Random replacement rules, then a medicine molecule made by applying `size`
+// of them starting from "e". Since every rule adds one element (not counting
+// Rn, Y and Ar), it takes exactly `size` steps to make.
+impl Generator for Day19 {
+	const DEFAULT_SIZE: usize = 200;
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		let mut rules: Vec<(&str, Vec<&str>)> = Vec::new();
+		for &element in &ELEMENTS {
+			for _ in 0..rng.between(1, 4) {
+				rules.push((element, random_replacement(rng)));
+			}
+		}
+		for _ in 0..rng.between(2, 3) {
+			rules.push(("e", vec![*rng.choose(&ELEMENTS), *rng.choose(&ELEMENTS)]));
+		}
+
+		let mut molecule = vec!["e"];
+		for _ in 0..size.max(1) {
+			let replaceable: Vec<usize> = (0..molecule.len())
+				.filter(|&idx| rules.iter().any(|&(input, _)| input == molecule[idx]))
+				.collect();
+			let idx = *rng.choose(&replaceable);
+			let options: Vec<&Vec<&str>> = rules.iter()
+				.filter(|&&(input, _)| input == molecule[idx])
+				.map(|(_, output)| output)
+				.collect();
+			let output = rng.choose(&options).to_vec();
+			molecule.splice(idx..idx + 1, output);
+		}
+
+		let mut lines: Vec<String> = rules.iter()
+			.map(|&(input, ref output)| format!("{} => {}", input, output.concat()))
+			.collect();
+		lines.push(String::new());
+		lines.push(molecule.concat());
+		lines.join("\n")
+	}
+}
+
+// Both parts against the brute force versions. The search in part 2 is
+// exponential, so molecules stay a handful of steps from "e".
+impl Differential for Day19 {
+	type Answer = (usize, Option<usize>);
+
+	const SIZES: RangeInclusive<usize> = 1..=6;
+
+	fn reference(input: &(Vec<Mapping>, String)) -> (usize, Option<usize>) {
+		let (ref mapping_list, ref molecule) = *input;
+		(replace_each_element(mapping_list, molecule), search_reduction_steps(mapping_list, molecule))
+	}
+
+	fn fast(input: &(Vec<Mapping>, String)) -> (usize, Option<usize>) {
+		(Day19::part1(input), Some(Day19::part2(input)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn example(molecule: &str) -> (Vec<Mapping>, String) {
+		let text = format!("H => HO\nH => OH\nO => HH\n\n{}", molecule);
+		Day19::parse(&PuzzleInput::from_text("example", &text).unwrap()).unwrap()
+	}
+
+	// The part 2 examples rely on "e => H" and "e => O", which the
+	// token-counting shortcut in `count_reduction_steps` doesn't model, so only
+	// part 1 is checked here.
+	#[test]
+	fn part1_examples() {
+		assert_eq!(Day19::part1(&example("HOH")), 4);
+		assert_eq!(Day19::part1(&example("HOHOHO")), 7);
+	}
+
+	#[test]
+	fn generated_inputs_solve() {
+		for seed in 0..20 {
+			let text = aoc_common::generate::<Day19>(seed, Some(50));
+			let input = Day19::parse(&PuzzleInput::from_text("generated", &text).unwrap()).unwrap();
+			assert!(Day19::part1(&input) > 0);
+			assert_eq!(Day19::part2(&input), 50);
+		}
+	}
+
+	#[test]
+	fn elements_of_molecule() {
+		assert_eq!(elements("CaRnHOAr"), ["Ca", "Rn", "H", "O", "Ar"]);
+	}
+
+	#[test]
+	fn counting_matches_search() {
+		if let Err(mismatch) = aoc_common::differential::check::<Day19>(0..20) {
+			panic!("{}", mismatch);
+		}
+	}
+}
@@ -0,0 +1,390 @@
+//! Day 2: I Was Told There Would Be No Math
+//!
+//! Wrapping paper and ribbon for a list of presents. Each present is a
+//! [`Package`], and [`report_csv`] breaks the totals down by present.
+//! [`sleigh`] plans how to load the presents into sleighs, and [`rolls`] how to
+//! cut their paper from rolls.
+
+extern crate aoc_common;
+#[macro_use] extern crate log;
+
+pub mod rolls;
+pub mod sleigh;
+
+use aoc_common::{Generator, ParseError, PuzzleInput, Rng, Solution, Tokens};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day2.txt");
+
+/// A present's dimensions. Every side is positive, and the package is small
+/// enough that its paper and ribbon fit in a `u64`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Package {
+	length: u64,
+	width: u64,
+	height: u64,
+}
+
+/// Totals for a list of presents.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Totals {
+	pub paper: u64,
+	pub ribbon: u64,
+}
+
+impl Package {
+	/// None if a side is 0, or the package is too big to measure.
+	pub fn new(length: u64, width: u64, height: u64) -> Option<Package> {
+		if length == 0 || width == 0 || height == 0 {
+			return None;
+		}
+		let faces = length.checked_mul(width)?
+			.checked_add(width.checked_mul(height)?)?
+			.checked_add(height.checked_mul(length)?)?;
+		let volume = length.checked_mul(width)?.checked_mul(height)?;
+		let sides = length.checked_add(width)?.checked_add(height)?;
+		// Paper is at most three times the faces, and ribbon at most the volume
+		// plus twice the sides, so if their sum fits then so does every amount.
+		faces.checked_mul(3)?.checked_add(volume)?.checked_add(sides.checked_mul(2)?)?;
+		Some(Package { length, width, height })
+	}
+
+	/// Reads `LxWxH`, allowing whitespace around each side, e.g. `2 x 3 x 4`.
+	pub fn parse(line: &mut Tokens) -> Result<Package, ParseError> {
+		let whole = line.rest("dimensions like 2x3x4")?;
+		let sides = whole.split('x');
+		if sides.len() != 3 {
+			return Err(line.error(whole, "three dimensions like 2x3x4"));
+		}
+
+		let mut dimensions = [0; 3];
+		for (dimension, side) in dimensions.iter_mut().zip(sides) {
+			let side = side.trim();
+			*dimension = line.parse::<u64>(side, "a whole number")?;
+			if *dimension == 0 {
+				return Err(line.error(side, "a side longer than 0"));
+			}
+		}
+		let [length, width, height] = dimensions;
+		Package::new(length, width, height).ok_or_else(|| line.error(whole, "a package small enough to measure"))
+	}
+
+	pub fn dimensions(&self) -> [u64; 3] {
+		[self.length, self.width, self.height]
+	}
+
+	// The two shortest sides.
+	fn smallest_face(&self) -> (u64, u64) {
+		let mut sides = self.dimensions();
+		sides.sort_unstable();
+		(sides[0], sides[1])
+	}
+
+	/// Paper to cover every face, without slack.
+	pub fn surface_area(&self) -> u64 {
+		2 * (self.length * self.width + self.width * self.height + self.height * self.length)
+	}
+
+	/// Extra paper: the area of the smallest face.
+	pub fn slack(&self) -> u64 {
+		let (first, second) = self.smallest_face();
+		first * second
+	}
+
+	/// Paper including slack.
+	pub fn paper(&self) -> u64 {
+		self.surface_area() + self.slack()
+	}
+
+	/// Ribbon around the smallest perimeter of any face, without the bow.
+	pub fn wrap(&self) -> u64 {
+		let (first, second) = self.smallest_face();
+		2 * (first + second)
+	}
+
+	/// Ribbon for the bow: the package's volume.
+	pub fn bow(&self) -> u64 {
+		self.length * self.width * self.height
+	}
+
+	/// Ribbon including the bow.
+	pub fn ribbon(&self) -> u64 {
+		self.wrap() + self.bow()
+	}
+}
+
+/// Paper and ribbon for every package, or None if a total doesn't fit in a
+/// `u64`.
+pub fn totals(packages: &[Package]) -> Option<Totals> {
+	packages.iter().try_fold(Totals::default(), |totals, package| {
+		trace!("{}x{}x{}: paper {}, ribbon {}",
+			package.length, package.width, package.height,
+			package.paper(), package.ribbon());
+		Some(Totals {
+			paper: totals.paper.checked_add(package.paper())?,
+			ribbon: totals.ribbon.checked_add(package.ribbon())?,
+		})
+	})
+}
+
+/// One CSV row per present with its dimensions, paper and ribbon, and a last
+/// row of totals. Paper includes the slack and ribbon includes the bow, so the
+/// totals are the puzzle's answers.
+pub fn report_csv(packages: &[Package]) -> String {
+	let mut csv = String::from("present,length,width,height,paper,slack,ribbon,bow\n");
+	let mut sums = [0u128; 4];
+	for (idx, package) in packages.iter().enumerate() {
+		let amounts = [package.paper(), package.slack(), package.ribbon(), package.bow()];
+		for (sum, &amount) in sums.iter_mut().zip(&amounts) {
+			*sum += amount as u128;
+		}
+		csv.push_str(&format!("{},{},{},{},{},{},{},{}\n",
+			idx + 1, package.length, package.width, package.height,
+			amounts[0], amounts[1], amounts[2], amounts[3]));
+	}
+	csv.push_str(&format!("total,,,,{},{},{},{}\n", sums[0], sums[1], sums[2], sums[3]));
+	csv
+}
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day2;
+
+impl Solution for Day2 {
+	type Input = Vec<Package>;
+	type Part1 = u64;
+	type Part2 = u64;
+
+	fn parse(input: &PuzzleInput) -> Result<Vec<Package>, ParseError> {
+		let packages = input.tokens()
+			.map(|mut line| Package::parse(&mut line))
+			.collect::<Result<Vec<Package>, ParseError>>()?;
+		if totals(&packages).is_none() {
+			return Err(input.last_line().error_at_end("presents needing fewer than 2^64 feet of paper and ribbon"));
+		}
+		Ok(packages)
+	}
+
+	fn part1(packages: &Vec<Package>) -> u64 {
+		totals(packages).expect("checked by parse").paper
+	}
+
+	fn part2(packages: &Vec<Package>) -> u64 {
+		totals(packages).expect("checked by parse").ribbon
+	}
+}
+
+// `size` presents, each side from 1 to 30.
+impl Generator for Day2 {
+	const DEFAULT_SIZE: usize = 1000;
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		let presents: Vec<String> = (0..size)
+			.map(|_| format!("{}x{}x{}", rng.between(1, 30), rng.between(1, 30), rng.between(1, 30)))
+			.collect();
+		presents.join("\n")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn example(text: &str) -> Vec<Package> {
+		Day2::parse(&PuzzleInput::from_text("example", text).unwrap()).unwrap()
+	}
+
+	fn parse_error(text: &str) -> String {
+		Day2::parse(&PuzzleInput::from_text("example", text).unwrap()).unwrap_err().to_string()
+	}
+
+	#[test]
+	fn part1_examples() {
+		assert_eq!(Day2::part1(&example("2x3x4")), 58);
+		assert_eq!(Day2::part1(&example("1x1x10")), 43);
+	}
+
+	#[test]
+	fn part2_examples() {
+		assert_eq!(Day2::part2(&example("2x3x4")), 34);
+		assert_eq!(Day2::part2(&example("1x1x10")), 14);
+	}
+
+	#[test]
+	fn packages_are_validated() {
+		assert_eq!(example(" 2 x 3 x4 \n1x1 x 10"), vec![Package::new(2, 3, 4).unwrap(), Package::new(1, 1, 10).unwrap()]);
+		assert_eq!(Day2::part1(&example("100000x100000x100000")), 70_000_000_000);
+
+		assert_eq!(parse_error("2x3"), "example:1:1: expected three dimensions like 2x3x4, found '2x3'");
+		assert_eq!(parse_error("2x 0 x4"), "example:1:4: expected a side longer than 0, found '0'");
+		assert_eq!(parse_error("2x-3x4"), "example:1:3: expected a whole number, found '-3'");
+		assert_eq!(parse_error("2xx4"), "example:1:3: expected a whole number, found nothing");
+		assert_eq!(parse_error("1x1x1\n\n1x1x1"), "example:2:1: expected dimensions like 2x3x4, found end of line");
+		assert_eq!(parse_error("4294967296x4294967296x2"),
+		           "example:1:1: expected a package small enough to measure, found '4294967296x4294967296x2'");
+		assert!(Package::new(1, 0, 1).is_none());
+	}
+
+	// Checks that every present is loaded once, inside the hold and without
+	// overlapping another, and returns how many sleighs the plan uses.
+	fn check_plan(packages: &[Package], plan: &sleigh::Plan) -> usize {
+		let mut presents: Vec<usize> = plan.placements.iter().map(|placement| placement.present).collect();
+		presents.sort_unstable();
+		assert_eq!(presents, (0..packages.len()).collect::<Vec<usize>>());
+		for (idx, placement) in plan.placements.iter().enumerate() {
+			let mut size = placement.size;
+			let mut sides = packages[placement.present].dimensions();
+			size.sort_unstable();
+			sides.sort_unstable();
+			assert_eq!(size, sides);
+			assert!((0..3).all(|axis| placement.position[axis] + placement.size[axis] <= plan.hold[axis]));
+			assert!(placement.sleigh < plan.sleighs);
+			for other in &plan.placements[idx + 1..] {
+				assert!(other.sleigh != placement.sleigh || !other.overlaps(placement), "{:?} {:?}", placement, other);
+			}
+		}
+		plan.sleighs
+	}
+
+	#[test]
+	fn sleigh_plans() {
+		use sleigh::{PackError, Rotation};
+
+		let cubes = vec![Package::new(1, 1, 1).unwrap(); 8];
+		let plan = sleigh::first_fit_decreasing(&cubes, [2, 2, 2], Rotation::Fixed).unwrap();
+		assert_eq!(check_plan(&cubes, &plan), 1);
+		assert_eq!(plan.utilisation(None), 100.0);
+
+		let long = [Package::new(3, 1, 1).unwrap()];
+		assert_eq!(sleigh::first_fit_decreasing(&long, [1, 3, 1], Rotation::Fixed).unwrap_err(),
+		           PackError::TooBig { present: 0 });
+		assert_eq!(sleigh::first_fit_decreasing(&long, [1, 3, 1], Rotation::Upright).unwrap().placements[0].size, [1, 3, 1]);
+		assert!(sleigh::first_fit_decreasing(&long, [1, 1, 3], Rotation::Upright).is_err());
+		assert_eq!(sleigh::exact(&long, [1, 1, 3], Rotation::Any).unwrap().placements[0].size, [1, 1, 3]);
+		assert_eq!(sleigh::exact(&cubes[..1].repeat(9), [3, 3, 3], Rotation::Any).unwrap_err(),
+		           PackError::TooMany { presents: 9, limit: sleigh::EXACT_LIMIT });
+
+		// Exactly one hold by volume, but the slabs and the 2x2x2 can't all share
+		let packages = example("3x3x1\n3x3x1\n2x2x2\n1x1x1");
+		let plan = sleigh::exact(&packages, [3, 3, 3], Rotation::Fixed).unwrap();
+		assert_eq!(check_plan(&packages, &plan), 2);
+		assert_eq!(plan.utilisation(None), 50.0);
+
+		// Loading the biggest first takes a sleigh more than it has to here
+		let packages = example("2x1x2\n2x3x4\n1x3x3\n4x2x4\n2x2x3\n4x1x4");
+		let heuristic = sleigh::first_fit_decreasing(&packages, [4, 4, 4], Rotation::Fixed).unwrap();
+		let exact = sleigh::exact(&packages, [4, 4, 4], Rotation::Fixed).unwrap();
+		assert_eq!((check_plan(&packages, &heuristic), check_plan(&packages, &exact)), (3, 2));
+	}
+
+	#[test]
+	fn exact_plans_are_never_worse() {
+		let mut rng = Rng::new(2);
+		for _ in 0..30 {
+			let count = rng.below(6) + 1;
+			let packages: Vec<Package> = (0..count)
+				.map(|_| Package::new(rng.between(1, 4) as u64, rng.between(1, 4) as u64, rng.between(1, 4) as u64).unwrap())
+				.collect();
+			for &rotation in &[sleigh::Rotation::Fixed, sleigh::Rotation::Any] {
+				let heuristic = sleigh::first_fit_decreasing(&packages, [4, 4, 4], rotation).unwrap();
+				let exact = sleigh::exact(&packages, [4, 4, 4], rotation).unwrap();
+				let packed: u64 = packages.iter().map(|package| package.bow()).sum();
+				assert!(check_plan(&packages, &exact) <= check_plan(&packages, &heuristic));
+				assert!(exact.sleighs as u64 >= packed.div_ceil(64));
+			}
+		}
+	}
+
+	// Whether `cuts` can be separated by cuts that go all the way across,
+	// trying every line between pieces that doesn't cross one.
+	fn guillotine(cuts: &[rolls::Cut]) -> bool {
+		if cuts.len() <= 1 {
+			return true;
+		}
+		(0..2).any(|axis| {
+			cuts.iter().any(|line| {
+				let at = line.position[axis] + line.size[axis];
+				let (before, after): (Vec<rolls::Cut>, Vec<rolls::Cut>) = cuts.iter().partition(|cut| cut.position[axis] < at);
+				!after.is_empty()
+					&& before.iter().all(|cut| cut.position[axis] + cut.size[axis] <= at)
+					&& guillotine(&before) && guillotine(&after)
+			})
+		})
+	}
+
+	fn check_layout(packages: &[Package], layout: &rolls::Layout) {
+		assert_eq!(layout.paper_needed(), Day2::part1(&packages.to_vec()) as u128);
+		for (idx, cut) in layout.cuts.iter().enumerate() {
+			let mut size = cut.size;
+			let mut sides = cut.piece.sides;
+			size.sort_unstable();
+			sides.sort_unstable();
+			assert_eq!(size, sides);
+			assert!(cut.position[0] + cut.size[0] <= layout.roll.width);
+			assert!(layout.roll.length.is_none_or(|length| cut.position[1] + cut.size[1] <= length));
+			for other in &layout.cuts[idx + 1..] {
+				let overlaps = (0..2).all(|axis| {
+					cut.position[axis] < other.position[axis] + other.size[axis]
+						&& other.position[axis] < cut.position[axis] + cut.size[axis]
+				});
+				assert!(other.roll != cut.roll || !overlaps, "{:?} {:?}", cut, other);
+			}
+		}
+		for roll in 0..layout.rolls {
+			let on_roll: Vec<rolls::Cut> = layout.cuts.iter().filter(|cut| cut.roll == roll).cloned().collect();
+			assert!(guillotine(&on_roll));
+		}
+	}
+
+	#[test]
+	fn paper_from_rolls() {
+		use rolls::Roll;
+
+		// Seven 1x1 pieces in rows of three
+		let cube = example("1x1x1");
+		let layout = rolls::cut(&cube, Roll { width: 3, length: None }).unwrap();
+		check_layout(&cube, &layout);
+		assert_eq!((layout.rolls, layout.length, layout.paper_used()), (1, 3, 9));
+		assert_eq!(layout.waste().0, 2);
+
+		// On rolls two long, the second roll counts in full even though only
+		// its first row is cut
+		let layout = rolls::cut(&cube, Roll { width: 3, length: Some(2) }).unwrap();
+		check_layout(&cube, &layout);
+		assert_eq!((layout.rolls, layout.length, layout.paper_used()), (2, 4, 12));
+		assert_eq!(layout.waste().0, 5);
+
+		let packages = example("2x3x4\n1x1x10\n5x2x2");
+		for &roll in &[Roll { width: 10, length: None }, Roll { width: 12, length: Some(9) }, Roll { width: 4, length: Some(10) }] {
+			let layout = rolls::cut(&packages, roll).unwrap();
+			check_layout(&packages, &layout);
+			assert!(layout.rolls >= 1);
+		}
+		let layout = rolls::cut(&packages, Roll { width: 4, length: Some(10) }).unwrap();
+		assert!(layout.rolls > 1);
+
+		// Four pieces around a square can't be cut apart with guillotine cuts
+		let piece = rolls::Piece { present: 0, slack: false, sides: [1, 2] };
+		let pinwheel: Vec<rolls::Cut> = [([0, 0], [2, 1]), ([2, 0], [1, 2]), ([1, 2], [2, 1]), ([0, 1], [1, 2]), ([1, 1], [1, 1])]
+			.iter()
+			.map(|&(position, size)| rolls::Cut { piece, roll: 0, position, size })
+			.collect();
+		assert!(!guillotine(&pinwheel));
+
+		let error = rolls::cut(&packages, Roll { width: 1, length: None }).unwrap_err();
+		assert_eq!(error.to_string(), "the 2x3 face of present 1 doesn't fit on the roll");
+
+		let text = aoc_common::generate::<Day2>(5, Some(100));
+		let generated = example(&text);
+		check_layout(&generated, &rolls::cut(&generated, Roll { width: 36, length: Some(200) }).unwrap());
+	}
+
+	#[test]
+	fn report_per_present() {
+		let packages = example("2x3x4\n1x1x10");
+		assert_eq!(report_csv(&packages),
+		           "present,length,width,height,paper,slack,ribbon,bow\n\
+		            1,2,3,4,58,6,34,24\n\
+		            2,1,1,10,43,1,14,10\n\
+		            total,,,,101,7,48,34\n");
+	}
+}
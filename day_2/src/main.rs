@@ -1,70 +1,133 @@
+extern crate aoc_common;
+extern crate day_2;
 
-use std::error::Error;
-use std::fs::File;
-use std::io::BufReader;
-use std::io::prelude::*;
-use std::path::Path;
+use std::env;
+use std::process;
 
-struct Size {
-	total_paper: i32,
-	total_ribbon: i32,
+use aoc_common::{LoadError, PuzzleInput, Solution, Tokens};
+use day_2::rolls::{self, Layout, Roll};
+use day_2::sleigh::{self, Plan, Rotation};
+use day_2::{Day2, Package};
+
+// Prints the answers, or with
+//
+//     day_2 --csv > report.csv
+//
+// the paper and ribbon for each present, or with
+//
+//     day_2 --sleigh 40x30x20 [--rotate fixed|upright|any] [--exact]
+//
+// a plan for loading the presents into sleighs with that size of hold. Presents
+// can be turned any way unless --rotate says otherwise. With
+//
+//     day_2 --roll 30 [--roll-length 1000]
+//
+// it lays out the paper on rolls that wide (and that long), and compares the
+// paper used with the answer to part 1.
+enum Report {
+	Answers,
+	Csv,
+	Sleighs { hold: [u64; 3], rotation: Rotation, exact: bool },
+	Rolls(Roll),
 }
 
-fn get_sizes(line: String) -> Size {
-	// Parse the file line as a vector of dimensions
-	// map() applies the function parse() to each element X in the split iterator.
-	let mut dimensions : Vec<i32> = line.split("x").map(|x| x.parse::<i32>().unwrap()).collect();
-	let l = dimensions[0];
-	let w = dimensions[1];
-	let h = dimensions[2];
-	
-	// Calculate paper
-	let sizes = vec![2*l*w, 2*w*h, 2*h*l];
-	
-	let mut min = std::cmp::min(sizes[0], sizes[1]);
-	min = std::cmp::min(min, sizes[2]) / 2;
-	
-	let sizes_sum = sizes.iter().fold(0, |sum, x| sum + x);
-	let total_paper = sizes_sum + min;
-	
-	println!("Paper: {}x{}x{} = {}+{}+{} + {} = {}", 
-		l, w, h,
-		sizes[0], sizes[1], sizes[2],
-		min, total_paper);
-	
-	// Calculate ribbon
-	dimensions.sort();
-	let smallest_face_perimeter = dimensions[0]*2 + dimensions[1]*2;
-	let volume = l*w*h;
-	let total_ribbon = volume + smallest_face_perimeter;
-	
-	return Size {total_paper: total_paper, total_ribbon: total_ribbon};
+// Reads the command line as if it were a line of input, for its diagnostics.
+fn read_args(args: &str) -> Result<Report, LoadError> {
+	let mut line = Tokens::new("command line", 1, args);
+	let mut report = Report::Answers;
+	let mut hold = None;
+	let mut rotation = Rotation::Any;
+	let mut exact = false;
+	let mut roll_length = None;
+	let expected = "--csv, --sleigh, --rotate, --exact, --roll or --roll-length";
+	while let Ok(token) = line.next(expected) {
+		match token.text {
+			"--csv" => report = Report::Csv,
+			"--sleigh" => {
+				let size = line.next("the hold's size, like 40x30x20")?;
+				hold = Some(Package::parse(&mut Tokens::new("--sleigh", 1, size.text))?.dimensions());
+			}
+			"--rotate" => {
+				let rule = line.next("fixed, upright or any")?;
+				rotation = match rule.text {
+					"fixed" => Rotation::Fixed,
+					"upright" => Rotation::Upright,
+					"any" => Rotation::Any,
+					_ => return Err(line.error(rule, "fixed, upright or any").into()),
+				};
+			}
+			"--exact" => exact = true,
+			"--roll" => report = Report::Rolls(Roll { width: line.number("the roll's width")?, length: None }),
+			"--roll-length" => roll_length = Some(line.number("the length of each roll")?),
+			_ => return Err(line.error(token, expected).into()),
+		}
+	}
+
+	match (hold, report) {
+		(Some(hold), _) => Ok(Report::Sleighs { hold, rotation, exact }),
+		(None, _) if exact || rotation != Rotation::Any => Err(line.error_at_end("--sleigh").into()),
+		(None, Report::Rolls(roll)) => Ok(Report::Rolls(Roll { length: roll_length, ..roll })),
+		(None, _) if roll_length.is_some() => Err(line.error_at_end("--roll").into()),
+		(None, report) => Ok(report),
+	}
 }
 
-fn main() {
-    // Create a path to the desired file
-    let path = Path::new("day2.txt");
-    let display = path.display();
+fn print_plan(packages: &[Package], plan: &Plan) {
+	for sleigh in 0..plan.sleighs {
+		let load: Vec<_> = plan.placements.iter().filter(|placement| placement.sleigh == sleigh).collect();
+		println!("sleigh {}: {} presents, {:.1}% full", sleigh + 1, load.len(), plan.utilisation(Some(sleigh)));
+		for placement in load {
+			let [length, width, height] = packages[placement.present].dimensions();
+			let [x, y, z] = placement.position;
+			let [along, across, up] = placement.size;
+			println!("  present {} ({}x{}x{}) at {},{},{} as {}x{}x{}",
+				placement.present + 1, length, width, height, x, y, z, along, across, up);
+		}
+	}
+	println!("{} sleighs, {:.1}% full", plan.sleighs, plan.utilisation(None));
+}
 
-    // Open the path in read-only mode, returns `io::Result<File>`
-    let file = match File::open(&path) {
-        // The `description` method of `io::Error` returns a string that describes the error
-        Err(why) => panic!("couldn't open {}: {}", display, Error::description(&why)),
-        Ok(file) => file,
-    };
-	
-	let reader = BufReader::new(file);
-	let lines = reader.lines(); 
-	
-	let mut total_paper = 0;
-	let mut total_ribbon = 0;
-	for l in lines {
-		let line = l.unwrap();
-		let package_sizes = get_sizes(line);
-		total_paper += package_sizes.total_paper;
-		total_ribbon += package_sizes.total_ribbon;
+fn print_layout(packages: &[Package], layout: &Layout) {
+	let (waste, percent) = layout.waste();
+	println!("pieces:       {}", layout.cuts.len());
+	println!("rolls:        {} ({} wide, {} long in all)", layout.rolls, layout.roll.width, layout.length);
+	println!("paper used:   {}", layout.paper_used());
+	println!("paper needed: {} (part 1: {})", layout.paper_needed(), Day2::part1(&packages.to_vec()));
+	println!("waste:        {} ({:.1}%)", waste, percent);
+}
+
+fn main() -> Result<(), LoadError> {
+	let report = read_args(&env::args().skip(1).collect::<Vec<String>>().join(" "))?;
+	let packages = Day2::parse(&PuzzleInput::from_path(day_2::INPUT)?)?;
+
+	match report {
+		Report::Answers => {
+			println!("day 2 part 1: {}", Day2::part1(&packages));
+			println!("day 2 part 2: {}", Day2::part2(&packages));
+		}
+		Report::Csv => print!("{}", day_2::report_csv(&packages)),
+		Report::Sleighs { hold, rotation, exact } => {
+			let plan = if exact {
+				sleigh::exact(&packages, hold, rotation)
+			} else {
+				sleigh::first_fit_decreasing(&packages, hold, rotation)
+			};
+			match plan {
+				Ok(plan) => print_plan(&packages, &plan),
+				Err(error) => {
+					eprintln!("error: {}", error);
+					process::exit(1);
+				}
+			}
+		}
+		Report::Rolls(roll) => match rolls::cut(&packages, roll) {
+			Ok(layout) => print_layout(&packages, &layout),
+			Err(error) => {
+				eprintln!("error: {}", error);
+				process::exit(1);
+			}
+		},
 	}
-		
-	println!("Total paper is {}", total_paper);
-	println!("Total ribbon is {}", total_ribbon);
-}
\ No newline at end of file
+
+	Ok(())
+}
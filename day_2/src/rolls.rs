@@ -0,0 +1,241 @@
+//! Lays out the wrapping paper for every present on rolls of a fixed width.
+//!
+//! Each present needs six faces and a piece of slack the size of its smallest
+//! face. Pieces are cut with guillotine cuts only: every cut goes all the way
+//! across the piece of paper being cut. The layout is in shelves: a cut across
+//! the roll takes off a shelf as long as its longest piece, cuts along the
+//! shelf split it into columns, and cuts across a column separate the pieces
+//! stacked in it. Each piece goes into the first column or shelf with room for
+//! it.
+//!
+//! Finding the layout that uses the least paper is hard, so [`cut`] is a
+//! heuristic: it tries a few orders to place the pieces in and both ways of
+//! turning them, and keeps the layout that uses the least paper. It isn't
+//! always the least possible.
+//!
+//! Positions are (across, along) the roll, from a corner of its start.
+
+use std::cmp::Reverse;
+use std::fmt;
+
+use crate::Package;
+
+/// Rolls of wrapping paper.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Roll {
+	pub width: u64,
+	/// How long each roll is, or None for one roll that never runs out.
+	pub length: Option<u64>,
+}
+
+/// One piece of paper a present needs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Piece {
+	/// Index into the list of presents.
+	pub present: usize,
+	/// Whether this is the slack rather than a face.
+	pub slack: bool,
+	pub sides: [u64; 2],
+}
+
+/// Where one piece is cut from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cut {
+	pub piece: Piece,
+	/// Which roll, from 0.
+	pub roll: usize,
+	pub position: [u64; 2],
+	/// The piece's sides across and along the roll, which may be turned.
+	pub size: [u64; 2],
+}
+
+/// Where every piece is cut from.
+#[derive(Clone, Debug)]
+pub struct Layout {
+	pub roll: Roll,
+	pub cuts: Vec<Cut>,
+	/// How many rolls are started.
+	pub rolls: usize,
+	/// Length of roll used up. A started roll counts in full, so whatever is
+	/// left at its end is an offcut; one roll that never runs out counts up
+	/// to the end of its last shelf.
+	pub length: u64,
+}
+
+impl Layout {
+	/// Area of the rolls used up, including offcuts and the ends of rolls.
+	pub fn paper_used(&self) -> u128 {
+		self.roll.width as u128 * self.length as u128
+	}
+
+	/// Area of the pieces themselves: the total paper of part 1.
+	pub fn paper_needed(&self) -> u128 {
+		self.cuts.iter().map(|cut| cut.size[0] as u128 * cut.size[1] as u128).sum()
+	}
+
+	/// Offcuts, as an area and as a percentage of the paper used.
+	pub fn waste(&self) -> (u128, f64) {
+		let waste = self.paper_used() - self.paper_needed();
+		if waste == 0 {
+			return (0, 0.0);
+		}
+		(waste, 100.0 * waste as f64 / self.paper_used() as f64)
+	}
+}
+
+/// A piece that can't be cut from the rolls.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TooBig {
+	pub piece: Piece,
+}
+
+impl fmt::Display for TooBig {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "the {}x{} {} of present {} doesn't fit on the roll",
+			self.piece.sides[0], self.piece.sides[1],
+			if self.piece.slack { "slack" } else { "face" },
+			self.piece.present + 1)
+	}
+}
+
+impl std::error::Error for TooBig {}
+
+/// The seven pieces of every present: two of each face, then the slack.
+pub fn pieces(packages: &[Package]) -> Vec<Piece> {
+	let mut pieces = Vec::with_capacity(packages.len() * 7);
+	for (present, package) in packages.iter().enumerate() {
+		let [length, width, height] = package.dimensions();
+		for &sides in &[[length, width], [width, height], [height, length]] {
+			for _ in 0..2 {
+				pieces.push(Piece { present, slack: false, sides });
+			}
+		}
+		let mut sides = package.dimensions();
+		sides.sort_unstable();
+		pieces.push(Piece { present, slack: true, sides: [sides[0], sides[1]] });
+	}
+	pieces
+}
+
+// Pieces stacked along the roll inside a shelf, as wide as the first of them.
+struct Column {
+	across: u64,
+	width: u64,
+	// Length taken so far
+	used: u64,
+}
+
+struct Shelf {
+	roll: usize,
+	// Where the shelf starts along its roll
+	start: u64,
+	length: u64,
+	columns: Vec<Column>,
+	// Width taken so far
+	used: u64,
+}
+
+impl Shelf {
+	// Cuts the piece from the first column with room for it, or from a new
+	// column if the shelf is wide enough. Returns where.
+	fn place(&mut self, [across, along]: [u64; 2], roll_width: u64) -> Option<[u64; 2]> {
+		if along > self.length {
+			return None;
+		}
+		if let Some(column) = self.columns.iter_mut().find(|column| column.width >= across && column.used + along <= self.length) {
+			let position = [column.across, self.start + column.used];
+			column.used += along;
+			return Some(position);
+		}
+		if self.used + across <= roll_width {
+			self.columns.push(Column { across: self.used, width: across, used: along });
+			let position = [self.used, self.start];
+			self.used += across;
+			return Some(position);
+		}
+		None
+	}
+}
+
+// Which pieces to place first: the longest along the roll, the widest across
+// it, or the biggest.
+#[derive(Clone, Copy)]
+enum Order {
+	Length,
+	Width,
+	Area,
+}
+
+const ORDERS: [Order; 3] = [Order::Length, Order::Width, Order::Area];
+
+/// Cuts every present's pieces from `roll`, keeping the layout that uses the
+/// least paper of the few the heuristic tries.
+pub fn cut(packages: &[Package], roll: Roll) -> Result<Layout, TooBig> {
+	let fits = |[across, along]: [u64; 2]| across <= roll.width && roll.length.is_none_or(|length| along <= length);
+	let pieces = pieces(packages);
+	if let Some(&piece) = pieces.iter().find(|piece| !fits(piece.sides) && !fits([piece.sides[1], piece.sides[0]])) {
+		return Err(TooBig { piece });
+	}
+
+	let mut best: Option<(Layout, u64)> = None;
+	for &long_across in &[true, false] {
+		// Each piece turned the preferred way if it fits that way
+		let sized: Vec<(Piece, [u64; 2])> = pieces.iter()
+			.map(|&piece| {
+				let [short, long] = if piece.sides[0] <= piece.sides[1] { piece.sides } else { [piece.sides[1], piece.sides[0]] };
+				let (preferred, other) = if long_across { ([long, short], [short, long]) } else { ([short, long], [long, short]) };
+				(piece, if fits(preferred) { preferred } else { other })
+			})
+			.collect();
+		for &order in &ORDERS {
+			let (layout, shelves) = lay_out(sized.clone(), roll, order);
+			// Ties on paper go to the layout with the least cut into shelves
+			let better = best.as_ref().is_none_or(|&(ref best, best_shelves)| {
+				(layout.paper_used(), shelves) < (best.paper_used(), best_shelves)
+			});
+			if better {
+				best = Some((layout, shelves));
+			}
+		}
+	}
+	Ok(best.expect("there's always a layout to try").0)
+}
+
+// Cuts the pieces, turned as given, in `order`. Also returns the length cut
+// into shelves.
+fn lay_out(mut sized: Vec<(Piece, [u64; 2])>, roll: Roll, order: Order) -> (Layout, u64) {
+	sized.sort_by_key(|&(piece, [across, along])| match order {
+		Order::Length => (Reverse(along), Reverse(across), piece.present),
+		Order::Width => (Reverse(across), Reverse(along), piece.present),
+		Order::Area => (Reverse(across * along), Reverse(along), piece.present),
+	});
+
+	let mut shelves: Vec<Shelf> = Vec::new();
+	let mut cuts = Vec::with_capacity(sized.len());
+	for (piece, size) in sized {
+		let mut spot = shelves.iter_mut()
+			.find_map(|shelf| shelf.place(size, roll.width).map(|position| (shelf.roll, position)));
+		if spot.is_none() {
+			// A new shelf after the last one, on a new roll if this one is used up
+			let (mut on_roll, mut start) = shelves.last().map_or((0, 0), |shelf| (shelf.roll, shelf.start + shelf.length));
+			if roll.length.is_some_and(|length| start + size[1] > length) {
+				on_roll += 1;
+				start = 0;
+			}
+			let mut shelf = Shelf { roll: on_roll, start, length: size[1], columns: Vec::new(), used: 0 };
+			spot = shelf.place(size, roll.width).map(|position| (on_roll, position));
+			shelves.push(shelf);
+		}
+		let (on_roll, position) = spot.expect("a piece always fits on a new shelf");
+		cuts.push(Cut { piece, roll: on_roll, position, size });
+	}
+
+	cuts.sort_by_key(|cut| (cut.roll, cut.position[1], cut.position[0]));
+	let rolls = shelves.last().map_or(0, |shelf| shelf.roll + 1);
+	let shelf_length = shelves.iter().map(|shelf| shelf.length).sum();
+	let length = match roll.length {
+		Some(length) => rolls as u64 * length,
+		None => shelf_length,
+	};
+	(Layout { roll, rolls, length, cuts }, shelf_length)
+}
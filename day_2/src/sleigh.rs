@@ -0,0 +1,338 @@
+//! Packs presents into sleighs.
+//!
+//! Presents are boxes loaded into a sleigh's cargo hold with their sides
+//! parallel to the hold's, without overlapping. [`first_fit_decreasing`] loads
+//! the biggest presents first, each into the first sleigh with room for it;
+//! [`exact`] finds the fewest sleighs possible for a handful of presents.
+//! Presents don't need anything under them, as if the hold were full of
+//! packing foam.
+//!
+//! Sizes and positions are (length, width, height), with the origin in a
+//! bottom corner of the hold.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+use crate::Package;
+
+/// Which ways up a present may be loaded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rotation {
+	/// As listed: the present's length along the hold's length, and so on.
+	Fixed,
+	/// Turned about the vertical only, so the height stays up.
+	Upright,
+	/// Any of the six ways.
+	Any,
+}
+
+impl Rotation {
+	// Every distinct way to load a present with these sides.
+	fn orientations(self, [length, width, height]: [u64; 3]) -> Vec<[u64; 3]> {
+		let mut orientations = match self {
+			Rotation::Fixed => vec![[length, width, height]],
+			Rotation::Upright => vec![[length, width, height], [width, length, height]],
+			Rotation::Any => vec![
+				[length, width, height], [length, height, width],
+				[width, length, height], [width, height, length],
+				[height, length, width], [height, width, length],
+			],
+		};
+		orientations.sort_unstable();
+		orientations.dedup();
+		orientations
+	}
+}
+
+/// Where one present goes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Placement {
+	/// Index into the list of presents.
+	pub present: usize,
+	/// Which sleigh, from 0.
+	pub sleigh: usize,
+	/// The present's corner nearest the origin.
+	pub position: [u64; 3],
+	/// The present's sides as loaded, which may be turned.
+	pub size: [u64; 3],
+}
+
+impl Placement {
+	/// Whether the two presents take up any of the same space.
+	pub fn overlaps(&self, other: &Placement) -> bool {
+		(0..3).all(|axis| {
+			self.position[axis] < other.position[axis] + other.size[axis]
+				&& other.position[axis] < self.position[axis] + self.size[axis]
+		})
+	}
+}
+
+/// Where every present goes, and how many sleighs that takes.
+#[derive(Clone, Debug)]
+pub struct Plan {
+	pub hold: [u64; 3],
+	pub sleighs: usize,
+	/// One per present, by sleigh and then by present.
+	pub placements: Vec<Placement>,
+}
+
+impl Plan {
+	fn new(hold: [u64; 3], mut placements: Vec<Placement>) -> Plan {
+		placements.sort_by_key(|placement| (placement.sleigh, placement.present));
+		let sleighs = placements.last().map_or(0, |placement| placement.sleigh + 1);
+		Plan { hold, sleighs, placements }
+	}
+
+	/// How much of the sleighs' holds is taken up by presents, from 0 to 100.
+	/// `sleigh` picks one sleigh, or None for all of them.
+	pub fn utilisation(&self, sleigh: Option<usize>) -> f64 {
+		let sleighs = if sleigh.is_some() { 1 } else { self.sleighs };
+		let capacity = volume(self.hold) as f64 * sleighs as f64;
+		if capacity == 0.0 {
+			return 0.0;
+		}
+		let used: u128 = self.placements
+			.iter()
+			.filter(|placement| sleigh.is_none_or(|sleigh| placement.sleigh == sleigh))
+			.map(|placement| volume(placement.size))
+			.sum();
+		100.0 * used as f64 / capacity
+	}
+}
+
+/// Why presents couldn't be planned.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PackError {
+	/// The present doesn't fit in an empty hold whichever way it's turned.
+	TooBig { present: usize },
+	/// Too many presents for `exact`.
+	TooMany { presents: usize, limit: usize },
+}
+
+impl fmt::Display for PackError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			PackError::TooBig { present } => write!(f, "present {} doesn't fit in the hold", present + 1),
+			PackError::TooMany { presents, limit } => {
+				write!(f, "{} presents are too many to plan exactly; the limit is {}", presents, limit)
+			}
+		}
+	}
+}
+
+impl std::error::Error for PackError {}
+
+/// The most presents [`exact`] will plan.
+pub const EXACT_LIMIT: usize = 8;
+
+fn volume(size: [u64; 3]) -> u128 {
+	size.iter().map(|&side| side as u128).product()
+}
+
+fn fits(size: [u64; 3], position: [u64; 3], hold: [u64; 3]) -> bool {
+	(0..3).all(|axis| position[axis] + size[axis] <= hold[axis])
+}
+
+// The ways each present can be loaded into an empty hold. Fails if a present
+// can't be loaded at all.
+fn orientations(packages: &[Package], hold: [u64; 3], rotation: Rotation) -> Result<Vec<Vec<[u64; 3]>>, PackError> {
+	packages.iter()
+		.enumerate()
+		.map(|(present, package)| {
+			let fitting: Vec<[u64; 3]> = rotation.orientations(package.dimensions())
+				.into_iter()
+				.filter(|&size| fits(size, [0; 3], hold))
+				.collect();
+			if fitting.is_empty() {
+				Err(PackError::TooBig { present })
+			} else {
+				Ok(fitting)
+			}
+		})
+		.collect()
+}
+
+// Presents from the biggest to the smallest, keeping the listed order for
+// presents of the same volume.
+fn by_volume(packages: &[Package], presents: impl Iterator<Item = usize>) -> Vec<usize> {
+	let mut presents: Vec<usize> = presents.collect();
+	presents.sort_by_key(|&present| std::cmp::Reverse(volume(packages[present].dimensions())));
+	presents
+}
+
+// A sleigh being loaded by the heuristic.
+struct Sleigh {
+	placed: Vec<Placement>,
+	// Corners of presents already loaded, where the next ones can go
+	corners: Vec<[u64; 3]>,
+}
+
+impl Sleigh {
+	fn new() -> Sleigh {
+		Sleigh { placed: Vec::new(), corners: vec![[0; 3]] }
+	}
+
+	// Loads the present at the lowest corner it fits, trying each way it can
+	// be turned. Returns whether it fit.
+	fn load(&mut self, present: usize, sleigh: usize, orientations: &[[u64; 3]], hold: [u64; 3]) -> bool {
+		self.corners.sort_unstable_by_key(|corner| (corner[2], corner[1], corner[0]));
+		for (idx, &position) in self.corners.iter().enumerate() {
+			for &size in orientations {
+				let placement = Placement { present, sleigh, position, size };
+				if !fits(size, position, hold) || self.placed.iter().any(|other| other.overlaps(&placement)) {
+					continue;
+				}
+
+				self.placed.push(placement);
+				self.corners.swap_remove(idx);
+				for axis in 0..3 {
+					let mut corner = position;
+					corner[axis] += size[axis];
+					if !self.corners.contains(&corner) {
+						self.corners.push(corner);
+					}
+				}
+				return true;
+			}
+		}
+		false
+	}
+}
+
+/// Loads the presents from the biggest to the smallest, each into the first
+/// sleigh that has room, opening a new sleigh when none do.
+pub fn first_fit_decreasing(packages: &[Package], hold: [u64; 3], rotation: Rotation) -> Result<Plan, PackError> {
+	let orientations = orientations(packages, hold, rotation)?;
+
+	let mut sleighs: Vec<Sleigh> = Vec::new();
+	for present in by_volume(packages, 0..packages.len()) {
+		let loaded = sleighs.iter_mut()
+			.enumerate()
+			.any(|(sleigh, loading)| loading.load(present, sleigh, &orientations[present], hold));
+		if !loaded {
+			let mut loading = Sleigh::new();
+			let fit = loading.load(present, sleighs.len(), &orientations[present], hold);
+			assert!(fit, "every present fits in an empty hold");
+			sleighs.push(loading);
+		}
+	}
+
+	Ok(Plan::new(hold, sleighs.into_iter().flat_map(|loading| loading.placed).collect()))
+}
+
+// Every sum of sides that `presents` could add up to along `axis`, up to
+// `length`. Some packing of the presents that fits has every present at
+// such sums, so these are the only positions worth trying.
+fn positions(presents: &[usize], orientations: &[Vec<[u64; 3]>], axis: usize, length: u64) -> Vec<u64> {
+	let mut sums = BTreeSet::new();
+	sums.insert(0);
+	for &present in presents {
+		let sides: BTreeSet<u64> = orientations[present].iter().map(|size| size[axis]).collect();
+		let longer: Vec<u64> = sums.iter()
+			.flat_map(|&sum| sides.iter().map(move |&side| sum + side))
+			.filter(|&sum| sum < length)
+			.collect();
+		sums.extend(longer);
+	}
+	sums.into_iter().collect()
+}
+
+// Tries every position for each of `presents` in turn, backtracking when one
+// doesn't fit.
+fn place(presents: &[usize],
+         orientations: &[Vec<[u64; 3]>],
+         positions: &[Vec<u64>; 3],
+         hold: [u64; 3],
+         placed: &mut Vec<Placement>)
+         -> bool {
+	let present = match presents.first() {
+		Some(&present) => present,
+		None => return true,
+	};
+	for &size in &orientations[present] {
+		for &z in &positions[2] {
+			for &y in &positions[1] {
+				for &x in &positions[0] {
+					let placement = Placement { present, sleigh: 0, position: [x, y, z], size };
+					if !fits(size, placement.position, hold) || placed.iter().any(|other| other.overlaps(&placement)) {
+						continue;
+					}
+					placed.push(placement);
+					if place(&presents[1..], orientations, positions, hold, placed) {
+						return true;
+					}
+					placed.pop();
+				}
+			}
+		}
+	}
+	false
+}
+
+// A way to load every present in `group` (a bitset) into one sleigh, if
+// there is one.
+fn load_one(packages: &[Package], group: u32, orientations: &[Vec<[u64; 3]>], hold: [u64; 3]) -> Option<Vec<Placement>> {
+	let presents = by_volume(packages, (0..packages.len()).filter(|&present| group & (1 << present) != 0));
+	let total: u128 = presents.iter().map(|&present| volume(packages[present].dimensions())).sum();
+	if total > volume(hold) {
+		return None;
+	}
+
+	let positions = [0, 1, 2].map(|axis| positions(&presents, orientations, axis, hold[axis]));
+	let mut placed = Vec::new();
+	if place(&presents, orientations, &positions, hold, &mut placed) {
+		Some(placed)
+	} else {
+		None
+	}
+}
+
+/// The fewest sleighs the presents fit in, by trying every way to split
+/// them between sleighs. Only for up to [`EXACT_LIMIT`] presents, and slow
+/// for big holds.
+pub fn exact(packages: &[Package], hold: [u64; 3], rotation: Rotation) -> Result<Plan, PackError> {
+	if packages.len() > EXACT_LIMIT {
+		return Err(PackError::TooMany { presents: packages.len(), limit: EXACT_LIMIT });
+	}
+	let orientations = orientations(packages, hold, rotation)?;
+
+	// For each set of presents (as a bitset), the fewest sleighs it takes and
+	// the presents that go in the first of them. Every present fits alone, so
+	// every set can be loaded somehow.
+	let all = (1u32 << packages.len()) - 1;
+	let mut fewest = vec![(0, 0); all as usize + 1];
+	let mut loads: HashMap<u32, Option<Vec<Placement>>> = HashMap::new();
+	for presents in 1..=all {
+		// The first sleigh always takes the lowest-numbered present
+		let lowest = presents & presents.wrapping_neg();
+		let others = presents ^ lowest;
+		let mut best = (usize::MAX, 0);
+		let mut subset = others;
+		loop {
+			let group = subset | lowest;
+			let sleighs = fewest[(presents ^ group) as usize].0 + 1;
+			if sleighs < best.0 {
+				let load = loads.entry(group).or_insert_with(|| load_one(packages, group, &orientations, hold));
+				if load.is_some() {
+					best = (sleighs, group);
+				}
+			}
+			if subset == 0 {
+				break;
+			}
+			subset = (subset - 1) & others;
+		}
+		fewest[presents as usize] = best;
+	}
+
+	let mut placements = Vec::new();
+	let mut left = all;
+	while left != 0 {
+		let group = fewest[left as usize].1;
+		let sleigh = fewest[all as usize].0 - fewest[left as usize].0;
+		let load = loads[&group].as_ref().expect("only groups that fit are chosen");
+		placements.extend(load.iter().map(|&placement| Placement { sleigh, ..placement }));
+		left ^= group;
+	}
+	Ok(Plan::new(hold, placements))
+}
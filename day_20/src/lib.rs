@@ -0,0 +1,157 @@
+//! Day 20: Infinite Elves and Infinite Houses
+//!
+//! The first house to get at least the target number of presents.
+
+extern crate aoc_common;
+#[macro_use] extern crate log;
+
+use aoc_common::{Generator, ParseError, PuzzleInput, Rng, Solution};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day20.txt");
+
+// There are infinitely many Elves, numbered starting with 1. Each Elf 
+// delivers presents equal to ten times his or her number at each house.
+
+fn calculate_infinate(target_num: usize) -> usize {
+
+	// The last house is gaurenteed to get house_num*10 presents,
+	// so use that as an upper bound on the number of houses to search.
+	let upper_bound = target_num / 10 + 1;
+	
+	let mut houses = vec![0; upper_bound];
+	
+	// One elf starts per house number, starting at 1.
+	let mut lowest_house_index = upper_bound;
+	for elf_num in 1..houses.len() {
+		
+		// Walk the elf along the houses
+		let mut house_num = elf_num;
+		while house_num < houses.len() {
+			
+			houses[house_num] += elf_num * 10;
+			
+			// Save the house index if it has the required number of presents.
+			// Future elves may visit lower houses, so we can't just exit here.
+			if houses[house_num] >= target_num {
+				lowest_house_index = std::cmp::min(lowest_house_index, house_num);
+			}
+			
+			house_num += elf_num;
+		}
+	}
+	
+	debug!("Part 1: House {} has {} presents", lowest_house_index, houses[lowest_house_index]);
+	lowest_house_index
+}
+
+
+fn calculate_limit_50(target_num: usize) -> usize {
+
+	// The last house is gaurenteed to get house_num*11 presents,
+	// so use that as an upper bound on the number of houses to search.
+	let upper_bound = f32::ceil(target_num as f32 / 11.0) as usize;
+	
+	let mut houses = vec![0; upper_bound];
+	
+	// One elf starts per house number, starting at 1.
+	let mut lowest_house_index = upper_bound;
+	for elf_num in 1..houses.len() {
+		
+		// Walk the elf along the houses
+		let mut house_num = elf_num;
+		let mut houses_visited = 0;
+		while house_num < houses.len() && houses_visited < 50 {
+			
+			houses[house_num] += elf_num * 11;
+			
+			// Save the house index if it has the required number of presents.
+			// Future elves may visit lower houses, so we can't just exit here.
+			if houses[house_num] >= target_num {
+				lowest_house_index = std::cmp::min(lowest_house_index, house_num);
+			}
+			
+			house_num += elf_num;
+			houses_visited += 1;
+		}
+	}
+	
+	debug!("Part 2: House {} has {} presents", lowest_house_index, houses[lowest_house_index]);
+	lowest_house_index
+}
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day20;
+
+impl Solution for Day20 {
+	type Input = usize;
+	type Part1 = usize;
+	type Part2 = usize;
+
+	// The input is the number of presents to look for
+	fn parse(input: &PuzzleInput) -> Result<usize, ParseError> {
+		let mut line = input.single_line()?;
+		let presents = line.number("a number of presents")?;
+		line.end()?;
+		Ok(presents)
+	}
+
+	fn part1(&target_num: &usize) -> usize {
+		calculate_infinate(target_num)
+	}
+
+	fn part2(&target_num: &usize) -> usize {
+		calculate_limit_50(target_num)
+	}
+}
+
+// A target number of presents between `size` / 2 and `size`.
+impl Generator for Day20 {
+	const DEFAULT_SIZE: usize = 34_000_000;
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		// Tiny targets leave the part 2 search with no houses to look at.
+		let size = size.clamp(2000, i32::MAX as usize) as i32;
+		rng.between(size / 2, size).to_string()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// Houses 1 to 9 get 10, 30, 40, 70, 60, 120, 80, 150 and 130 presents.
+	#[test]
+	fn part1_examples() {
+		let cases = [(10, 1), (30, 2), (40, 3), (60, 4), (70, 4), (80, 6), (120, 6), (130, 8), (150, 8)];
+		for &(presents, house) in &cases {
+			assert_eq!(Day20::part1(&presents), house, "{} presents", presents);
+		}
+	}
+
+	// The first house to get `target` presents, adding up each house's elves
+	// one divisor at a time. Each elf leaves `per_elf` times its number, and
+	// stops after `visits` houses if there's a limit.
+	fn first_house(target: usize, per_elf: usize, visits: Option<usize>) -> usize {
+		(1..)
+			.find(|&house| {
+				let presents: usize = (1..=house)
+					.filter(|&elf| house % elf == 0 && visits.is_none_or(|visits| house / elf <= visits))
+					.map(|elf| elf * per_elf)
+					.sum();
+				presents >= target
+			})
+			.unwrap()
+	}
+
+	#[test]
+	fn generated_targets_match_counting_divisors() {
+		for seed in 0..5 {
+			let text = aoc_common::generate::<Day20>(seed, Some(20_000));
+			let target = Day20::parse(&PuzzleInput::from_text("generated", &text).unwrap()).unwrap();
+			assert!((10_000..=20_000).contains(&target));
+			assert_eq!(Day20::part1(&target), first_house(target, 10, None), "seed {}", seed);
+			assert_eq!(Day20::part2(&target), first_house(target, 11, Some(50)), "seed {}", seed);
+		}
+	}
+}
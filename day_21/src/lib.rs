@@ -0,0 +1,276 @@
+//! Day 21: RPG Simulator 20XX
+//!
+//! The cheapest equipment that wins a fight against the boss, and the priciest
+//! that loses.
+
+extern crate aoc_common;
+#[macro_use] extern crate log;
+
+use std::{cmp, fmt};
+
+use aoc_common::{Generator, ParseError, PuzzleInput, Rng, Solution, Token};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+/// Hit points, damage and armor of the player or the boss.
+#[derive(Copy,Clone)]
+pub struct Stats {
+    health: i32,
+    attack: i32,
+    defence: i32,
+}
+
+impl Stats {
+    fn add_item(&mut self, item: &Item) {
+        self.attack += item.damage;
+        self.defence += item.armor;
+    }
+}
+
+#[derive(PartialEq,Eq)]
+struct Item {
+    name: String,
+    cost: i32,
+    damage: i32,
+    armor: i32,
+}
+
+impl Item {
+    fn new(name: &str, cost: i32, damage: i32, armor: i32 ) -> Item {
+        Item{name: String::from(name), cost, damage, armor}
+    }
+}
+
+impl fmt::Display for Item {
+    // This trait requires `fmt` with this exact signature.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Write strictly the first element into the supplied output
+        // stream: `f`. Returns `fmt::Result` which indicates whether the
+        // operation succeeded or failed. Note that `write!` uses syntax which
+        // is very similar to `println!`.
+        write!(f, "{}", self.name)
+    }
+}
+
+// Player 1 attacks first, and thus wins on ties.
+// Returns 1 if player 1 wins, 2 if player 2 wins.
+fn player_wins(player: Stats, boss: Stats) -> bool {
+    let player_hp_loss_per_hit = cmp::max(1, boss.attack - player.defence);
+    let boss_hp_loss_per_hit = cmp::max(1, player.attack - boss.defence);
+
+    // Hits each side needs to take before it's dead, counting a last hit that
+    // leaves them below 0.
+    let boss_dead_after = (boss.health + boss_hp_loss_per_hit - 1) / boss_hp_loss_per_hit;
+    let player_dead_after = (player.health + player_hp_loss_per_hit - 1) / player_hp_loss_per_hit;
+    
+    trace!("player hp loss {}, boss hp loss {}", player_hp_loss_per_hit, boss_hp_loss_per_hit);
+    trace!("boss dead after {} hits, player dead after {} hits", boss_dead_after, player_dead_after);
+    
+    boss_dead_after <= player_dead_after
+}
+
+// Returns the lowest cost to win and the highest cost to still lose.
+fn search_equipment(boss: Stats) -> (i32, i32) {
+
+    let weapons = vec![
+        Item::new("Dagger",      8, 4, 0),
+        Item::new("Shortsword", 10, 5, 0),
+        Item::new("Warhammer",  25, 6, 0),
+        Item::new("Longsword",  40, 7, 0),
+        Item::new("Greataxe",   74, 8, 0),
+    ];
+    
+    let armors = vec![
+        Item::new("no armor",     0, 0, 0), // Armor is optional
+        Item::new("Leather",     13, 0, 1),
+        Item::new("Chainmail",   31, 0, 2),
+        Item::new("Splintmail",  53, 0, 3),
+        Item::new("Bandedmail",  75, 0, 4),
+        Item::new("Platemail",  102, 0, 5),
+    ];
+    
+    let rings = [
+        Item::new("no ring",     0, 0, 0),  // Rings are optional
+        Item::new("no ring",     0, 0, 0),
+        Item::new("Damage +1",  25, 1, 0),
+        Item::new("Damage +2",  50, 2, 0),
+        Item::new("Damage +3", 100, 3, 0),
+        Item::new("Defence +1", 20, 0, 1),
+        Item::new("Defence +2", 50, 0, 2),
+        Item::new("Defence +3", 80, 0, 3),
+    ];
+    
+    
+    let base_player = Stats {health: 100, attack: 0, defence: 0};
+    
+    let mut lowest_cost_to_win = i32::MAX;
+    let mut highest_cost_and_lose = 0;
+    
+    // There are few enough possible combinations for a brute-force search for the lowest cost to win.
+    for weapon in &weapons {
+        for armor in &armors {
+            // Two different slots of the list, so at most one of each ring
+            // but both can be empty.
+            for (ring1_idx, ring1) in rings.iter().enumerate() {
+                for ring2 in &rings[ring1_idx + 1..] {
+                    let mut player = base_player;
+                    player.add_item(weapon);
+                    player.add_item(armor);
+                    player.add_item(ring1);
+                    player.add_item(ring2);
+                    let cost = weapon.cost + armor.cost + ring1.cost + ring2.cost;
+                    
+                    let victory = player_wins(player, boss);
+                    
+                    if victory && cost < lowest_cost_to_win {
+                        debug!("Victory! for {} gold with {}, {}, {}, {},", cost, weapon, armor, ring1, ring2);
+                        lowest_cost_to_win = cost;
+                    }
+                    
+                    if !victory && cost > highest_cost_and_lose {
+                        debug!("Loss! for {} gold with {}, {}, {}, {},", cost, weapon, armor, ring1, ring2);
+                        highest_cost_and_lose = cost;
+                    }
+                }
+            }
+        }
+    }
+
+    (lowest_cost_to_win, highest_cost_and_lose)
+}
+
+// Reads a "Name: value" line from the boss's stats.
+fn read_stat(input: &PuzzleInput, name: &str) -> Result<Option<i32>, ParseError> {
+    for line in input.tokens() {
+        let text = line.text();
+        let (key, value) = match text.split_once(':') {
+            Some(pair) => pair,
+            None => {
+                let found = format!("'{}'", text);
+                return Err(line.error_at(0, text.chars().count(), "a stat like 'Damage: 8'", &found));
+            }
+        };
+
+        if key.trim() == name {
+            let offset = text.len() - value.trim_start().len();
+            let token = Token { text: value.trim(), offset };
+            return line.parse(token, "a number").map(Some);
+        }
+    }
+    Ok(None)
+}
+
+// Like `read_stat`, but the stat has to be there.
+fn require_stat(input: &PuzzleInput, name: &str) -> Result<i32, ParseError> {
+    read_stat(input, name)?
+        .ok_or_else(|| input.last_line().error_at_end(&format!("a '{}:' line", name)))
+}
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Stats;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    // Fights are won on whole hits, and both ring slots can be empty.
+    const VERSION: u32 = 2;
+
+    // Hit Points: 100
+    // Damage: 8
+    // Armor: 2
+    fn parse(input: &PuzzleInput) -> Result<Stats, ParseError> {
+        Ok(Stats {
+            health: require_stat(input, "Hit Points")?,
+            attack: require_stat(input, "Damage")?,
+            defence: require_stat(input, "Armor")?,
+        })
+    }
+
+    fn part1(&boss: &Stats) -> i32 {
+        let (lowest_cost_to_win, _) = search_equipment(boss);
+        lowest_cost_to_win
+    }
+
+    fn part2(&boss: &Stats) -> i32 {
+        let (_, highest_cost_and_lose) = search_equipment(boss);
+        highest_cost_and_lose
+    }
+}
+
+// A boss with `size` hit points, like the real bosses' 100 or so.
+impl Generator for Day21 {
+    const DEFAULT_SIZE: usize = 104;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        format!("Hit Points: {}\nDamage: {}\nArmor: {}", size.max(1), rng.between(4, 9), rng.between(0, 3))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_fight() {
+        let player = Stats { health: 8, attack: 5, defence: 5 };
+        let boss = Stats { health: 12, attack: 7, defence: 2 };
+        assert!(player_wins(player, boss));
+    }
+
+    // Fights a hit at a time, player first. True if the player wins.
+    fn fight(mut player: Stats, mut boss: Stats) -> bool {
+        loop {
+            boss.health -= cmp::max(1, player.attack - boss.defence);
+            if boss.health <= 0 {
+                return true;
+            }
+            player.health -= cmp::max(1, boss.attack - player.defence);
+            if player.health <= 0 {
+                return false;
+            }
+        }
+    }
+
+    // Every shopping trip the rules allow: one weapon, up to one armor and up
+    // to two different rings. Gives the cost, damage and armor of each.
+    fn shopping_trips() -> Vec<(i32, i32, i32)> {
+        let weapons = [(8, 4), (10, 5), (25, 6), (40, 7), (74, 8)];
+        let armors = [(0, 0), (13, 1), (31, 2), (53, 3), (75, 4), (102, 5)];
+        let rings = [(25, 1, 0), (50, 2, 0), (100, 3, 0), (20, 0, 1), (50, 0, 2), (80, 0, 3)];
+        let mut ring_sets = vec![(0, 0, 0)];
+        for (idx, &(cost, damage, armor)) in rings.iter().enumerate() {
+            ring_sets.push((cost, damage, armor));
+            for &(other_cost, other_damage, other_armor) in &rings[idx + 1..] {
+                ring_sets.push((cost + other_cost, damage + other_damage, armor + other_armor));
+            }
+        }
+
+        let mut trips = Vec::new();
+        for &(weapon_cost, damage) in &weapons {
+            for &(armor_cost, armor) in &armors {
+                for &(rings_cost, ring_damage, ring_armor) in &ring_sets {
+                    trips.push((weapon_cost + armor_cost + rings_cost, damage + ring_damage, armor + ring_armor));
+                }
+            }
+        }
+        trips
+    }
+
+    #[test]
+    fn generated_bosses_match_every_fight() {
+        for seed in 0..20 {
+            let text = aoc_common::generate::<Day21>(seed, None);
+            let boss = Day21::parse(&PuzzleInput::from_text("generated", &text).unwrap()).unwrap();
+            assert_eq!(boss.health, 104);
+
+            let (wins, losses): (Vec<_>, Vec<_>) = shopping_trips()
+                .into_iter()
+                .partition(|&(_, attack, defence)| fight(Stats { health: 100, attack, defence }, boss));
+            assert_eq!(Day21::part1(&boss), wins.iter().map(|trip| trip.0).min().unwrap(), "seed {}", seed);
+            assert_eq!(Day21::part2(&boss), losses.iter().map(|trip| trip.0).max().unwrap_or(0), "seed {}", seed);
+        }
+    }
+}
@@ -0,0 +1,413 @@
+//! Day 22: Wizard Simulator 20XX
+//!
+//! The least mana needed to win a spell-casting fight against the boss.
+
+extern crate aoc_common;
+#[macro_use] extern crate log;
+
+use std::{cmp, fmt};
+
+use aoc_common::{Generator, ParseError, PuzzleInput, Rng, Solution, Token};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+#[derive(Copy,Clone,PartialEq,Eq,Debug)]
+enum Character {
+    Player,
+    Boss,
+}
+
+/// Hit points, damage, armor and mana of the player or the boss.
+#[derive(Copy,Clone)]
+pub struct Stats {
+    health: i32,
+    attack: i32,
+    armor: i32,
+    mana: i32,
+}
+
+#[derive(Clone,PartialEq,Eq,Debug)]
+struct Spell {
+    name: String,
+    cost: i32, // Cost to cast
+    duration: i32, // 0 means instant
+    damage: i32, // Damage done to enemy
+    healing: i32, // Healing factor for yourself
+    armor: i32, // Bonus armor amount
+    mana: i32, // Recharge amount
+}
+
+impl Spell {
+    fn new(name: &str,
+           cost: i32,
+           duration: i32,
+           damage: i32,
+           healing: i32,
+           armor: i32,
+           mana: i32)
+           -> Spell {
+        Spell {
+            name: String::from(name),
+            cost,
+            duration,
+            damage,
+            healing,
+            armor,
+            mana,
+        }
+    }
+}
+impl Spell {
+    fn cast_message(&self) -> String {
+        let mut message = format!("Player casts {}", self);
+        if self.armor > 0 {
+            message += &format!(", increasing armor by {}", self.armor);
+        }
+        if self.damage > 0 && self.duration == 0 {
+            message += &format!(", dealing {} damage", self.damage);
+        }
+        if self.healing > 0 && self.duration == 0 {
+            message += &format!(", and healing {} hit points", self.healing);
+        }
+        message + "."
+    }
+}
+impl fmt::Display for Spell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+#[derive(Clone)]
+struct Effect {
+    spell: Spell,
+    turns_remaining: i32,
+}
+
+#[derive(Clone)]
+struct BattleState {
+    player: Stats,
+    boss: Stats,
+    effects: Vec<Effect>,
+    turn: i32,
+}
+impl BattleState {
+    fn log_state(&self, active_character: Character) {
+        trace!("--{:?} turn {}--", active_character, self.turn);
+        trace!("Player has {} hit points, {} armor, {} mana",
+               self.player.health,
+               self.player.armor,
+               self.player.mana);
+        trace!("{} active spells", self.effects.len());
+        trace!("Boss has {} hit points", self.boss.health);
+    }
+
+    fn apply_ongoing_effects(&mut self) {
+        for effect in self.effects.iter_mut() {
+            effect.turns_remaining -= 1;
+
+            if effect.spell.armor > 0 {
+                trace!("Shield's timer is now {}.", effect.turns_remaining);
+                if effect.turns_remaining > 0 {
+                    self.player.armor = effect.spell.armor;
+                } else {
+                    trace!("Shield wears off, decreasing armor by 7.");
+                    self.player.armor -= effect.spell.armor;
+                }
+            }
+            if effect.spell.damage > 0 {
+                self.boss.health -= effect.spell.damage;
+                trace!("Poison deals 3 damage; its timer is now {}.",
+                       effect.turns_remaining);
+                trace!("Boss health now {}.", self.boss.health);
+            }
+            if effect.spell.mana > 0 {
+                self.player.mana += effect.spell.mana;
+                trace!("Recharge provides 101 mana (now {}); its timer is now {}",
+                       self.player.mana,
+                       effect.turns_remaining);
+            }
+        }
+        // Remove timed-out effects
+        self.effects.retain(|s| s.turns_remaining > 0);
+    }
+
+    fn is_spell_active(&self, spell: &Spell) -> bool {
+        self.effects.iter().any(|s| s.spell.name == spell.name)
+    }
+}
+
+// Run a round of battle and return the lowest amount of mana used to win.
+// i32::max_value indicates a loss.
+fn take_turn(initial_state: &BattleState,
+             active_character: Character,
+             possible_spells: &[Spell],
+             is_hard_mode: bool)
+             -> i32 {
+
+    let mut state = initial_state.clone();
+
+    // Hard mode: subtract 1 HP at the start of each turn
+    if is_hard_mode && active_character == Character::Player {
+        state.player.health -= 1;
+        if state.player.health <= 0 {
+            trace!("##### Player died #####");
+            return i32::MAX;
+        }
+    }
+
+    state.log_state(active_character);
+    state.apply_ongoing_effects();
+
+    // Check for victory from spell effects
+    if state.boss.health <= 0 {
+        // Player wins
+        trace!("***** Boss died *****");
+        return 0;
+    }
+
+    // if Player is active, cast spell
+    if active_character == Character::Player {
+
+        if state.player.mana < 53 {
+            trace!("##### Insufficent mana of {}. Player died #####",
+                   state.player.mana);
+            return i32::MAX;
+        }
+
+        // Player casts spell
+        let mut current_lowest_cost = i32::MAX;
+        for spell_to_cast in possible_spells {
+
+            let mut next_state = state.clone();
+
+            if spell_to_cast.cost <= next_state.player.mana &&
+               !next_state.is_spell_active(spell_to_cast) {
+
+                // Check for victory from spell effects
+                if next_state.boss.health <= 0 {
+                    // Player wins
+                    // Didn't need to cast any spells, so zero cost. No need to continue checking.
+                    return 0;
+                }
+
+                trace!("{}", spell_to_cast.cast_message());
+
+                // Subtract casting cost
+                next_state.player.mana -= spell_to_cast.cost;
+
+                if spell_to_cast.duration > 0 {
+                    // Store delayed effects
+                    next_state.effects.push(Effect {
+                        spell: spell_to_cast.clone(),
+                        turns_remaining: spell_to_cast.duration,
+                    });
+                } else {
+                    // Apply immediate effects
+                    next_state.boss.health -= spell_to_cast.damage;
+                    next_state.player.health += spell_to_cast.healing;
+                }
+
+                // If boss is still alive, continue battle
+                if next_state.boss.health > 0 {
+                    next_state.turn += 1;
+
+                    let recursive_result = take_turn(&next_state,
+                                                     Character::Boss,
+                                                     possible_spells,
+                                                     is_hard_mode);
+                    let total_cost = i32::saturating_add(recursive_result, spell_to_cast.cost);
+
+                    current_lowest_cost = cmp::min(total_cost, current_lowest_cost);
+                } else {
+                    // Player wins
+                    trace!("***** Boss died *****");
+                    current_lowest_cost = cmp::min(spell_to_cast.cost, current_lowest_cost);
+                }
+            }
+        }
+        current_lowest_cost
+    } else {
+        // Boss's turn
+        let mut next_state = state.clone();
+
+        // Calculate damage
+        let damage = next_state.boss.attack - next_state.player.armor;
+        let damage = cmp::max(1, damage);
+        next_state.player.health -= damage;
+        trace!("Boss attacks for {} damage.", damage);
+
+        if next_state.player.health > 0 {
+            // Player is still alive to take a turn
+            next_state.turn += 1;
+            take_turn(&next_state,
+                      Character::Player,
+                      possible_spells,
+                      is_hard_mode)
+        } else {
+            trace!("##### Player died #####");
+            i32::MAX    // Player died
+        }
+    }
+}
+
+
+
+fn get_spells() -> Vec<Spell> {
+    // Magic Missile costs 53 mana. It instantly does 4 damage.
+    // Drain costs 73 mana. It instantly does 2 damage and heals you for 2 hit points.
+    // Shield costs 113 mana. It starts an effect that lasts for 6 turns. While it is active, your armor is increased by 7.
+    // Poison costs 173 mana. It starts an effect that lasts for 6 turns. At the start of each turn while it is active, it deals the boss 3 damage.
+    // Recharge costs 229 mana. It starts an effect that lasts for 5 turns. At the start of each turn while it is active, it gives you 101 new mana.
+
+    // name, cost, duration, damage, healing, armor, mana
+    vec![
+        Spell::new("Magic Missle", 53, 0, 4, 0, 0, 0),
+        Spell::new("Drain",        73, 0, 2, 2, 0, 0),
+        Spell::new("Shield",      113, 6, 0, 0, 7, 0),
+        Spell::new("Poison",      173, 6, 3, 0, 0, 0),
+        Spell::new("Recharge",    229, 5, 0, 0, 0, 101),
+    ]
+}
+
+fn get_initial_state(starting_boss: Stats) -> BattleState {
+    // You start with 50 hit points and 500 mana points.
+    let starting_player = Stats {
+        health: 50,
+        attack: 0,
+        armor: 0,
+        mana: 500,
+    };
+
+    // // Test characters
+    // let starting_boss = Stats {
+    // health: 14,
+    // attack: 8,
+    // armor: 0,
+    // mana: 0,
+    // };
+    // let starting_player = Stats {
+    // health: 10,
+    // attack: 0,
+    // armor: 0,
+    // mana: 250,
+    // };
+
+
+    BattleState {
+        player: starting_player,
+        boss: starting_boss,
+        effects: Vec::new(),
+        turn: 0,
+    }
+}
+
+// Reads a "Name: value" line from the boss's stats.
+fn read_stat(input: &PuzzleInput, name: &str) -> Result<Option<i32>, ParseError> {
+    for line in input.tokens() {
+        let text = line.text();
+        let (key, value) = match text.split_once(':') {
+            Some(pair) => pair,
+            None => {
+                let found = format!("'{}'", text);
+                return Err(line.error_at(0, text.chars().count(), "a stat like 'Damage: 8'", &found));
+            }
+        };
+
+        if key.trim() == name {
+            let offset = text.len() - value.trim_start().len();
+            let token = Token { text: value.trim(), offset };
+            return line.parse(token, "a number").map(Some);
+        }
+    }
+    Ok(None)
+}
+
+// Like `read_stat`, but the stat has to be there.
+fn require_stat(input: &PuzzleInput, name: &str) -> Result<i32, ParseError> {
+    read_stat(input, name)?
+        .ok_or_else(|| input.last_line().error_at_end(&format!("a '{}:' line", name)))
+}
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Stats;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    // Hit Points: 71
+    // Damage: 10
+    fn parse(input: &PuzzleInput) -> Result<Stats, ParseError> {
+        Ok(Stats {
+            health: require_stat(input, "Hit Points")?,
+            attack: require_stat(input, "Damage")?,
+            armor: read_stat(input, "Armor")?.unwrap_or(0),
+            mana: 0,
+        })
+    }
+
+    fn part1(&boss: &Stats) -> i32 {
+        take_turn(&get_initial_state(boss),
+                  Character::Player,
+                  &get_spells(),
+                  false)
+    }
+
+    fn part2(&boss: &Stats) -> i32 {
+        take_turn(&get_initial_state(boss),
+                  Character::Player,
+                  &get_spells(),
+                  true)
+    }
+}
+
+// A boss with `size` hit points who deals 10 damage. The search tries every
+// order of spells, and a weaker boss lets the player live longer, so damage
+// below 10 can take minutes to search, and above 10 often can't be beaten.
+impl Generator for Day22 {
+    const DEFAULT_SIZE: usize = 58;
+
+    fn generate(_rng: &mut Rng, size: usize) -> String {
+        format!("Hit Points: {}\nDamage: 10", size.max(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Both examples give the player 10 hit points and 250 mana.
+    fn cheapest_win(boss_health: i32) -> i32 {
+        let state = BattleState {
+            player: Stats { health: 10, attack: 0, armor: 0, mana: 250 },
+            boss: Stats { health: boss_health, attack: 8, armor: 0, mana: 0 },
+            effects: Vec::new(),
+            turn: 0,
+        };
+        take_turn(&state, Character::Player, &get_spells(), false)
+    }
+
+    // Poison, then Magic Missile
+    #[test]
+    fn first_example() {
+        assert_eq!(cheapest_win(13), 173 + 53);
+    }
+
+    // Recharge, Shield, Drain, Poison, then Magic Missile
+    #[test]
+    fn second_example() {
+        assert_eq!(cheapest_win(14), 229 + 113 + 73 + 173 + 53);
+    }
+
+    #[test]
+    fn default_generated_boss_solves() {
+        let text = aoc_common::generate::<Day22>(0, None);
+        let boss = Day22::parse(&PuzzleInput::from_text("generated", &text).unwrap()).unwrap();
+        assert_eq!((boss.health, boss.attack), (58, 10));
+        assert_eq!(Day22::part1(&boss), 1309);
+        assert_eq!(Day22::part2(&boss), 1309);
+    }
+}
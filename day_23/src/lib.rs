@@ -0,0 +1,294 @@
+//! Day 23: Opening the Turing Lock
+//!
+//! An interpreter for a two-register machine; [`process_instruction`] runs a
+//! single step.
+
+extern crate aoc_common;
+#[macro_use] extern crate log;
+
+use aoc_common::{Generator, ParseError, PuzzleInput, Rng, Solution, Tokens};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day23.txt");
+
+/// Register constant table
+#[derive(Copy,Clone,Debug)]
+pub enum Registers {
+    RegA = 0,
+    RegB = 1,
+}
+
+/// An instruction's operation.
+#[derive(Copy,Clone,Debug)]
+pub enum Command {
+    Hlf, /* hlf r sets register r to half its current value, then continues with the next instruction. */
+    Tpl, /* tpl r sets register r to triple its current value, then continues with the next instruction. */
+    Inc, // inc r increments register r, adding 1 to it, then continues with the next instruction.
+    Jmp, /* jmp offset is a jump; it continues with the instruction offset away relative to itself. */
+    Jie, // jie r, offset is like jmp, but only jumps if register r is even ("jump if even").
+    Jio, // jio r, offset is like jmp, but only jumps if register r is 1 ("jump if one", not odd).
+}
+impl Command {
+    fn parse(line: &mut Tokens) -> Result<Command, ParseError> {
+        let expected = "one of hlf, tpl, inc, jmp, jie or jio";
+        let token = line.next(expected)?;
+        match token.text {
+            "hlf" => Ok(Command::Hlf),
+            "tpl" => Ok(Command::Tpl),
+            "inc" => Ok(Command::Inc),
+            "jmp" => Ok(Command::Jmp),
+            "jie" => Ok(Command::Jie),
+            "jio" => Ok(Command::Jio),
+            _ => Err(line.error(token, expected)),
+        }
+    }
+}
+
+// A register name, followed by a comma when an offset comes next.
+fn parse_register(line: &mut Tokens, comma: bool) -> Result<i32, ParseError> {
+    let expected = if comma { "'a,' or 'b,'" } else { "'a' or 'b'" };
+    let token = line.next(expected)?;
+    match (token.text, comma) {
+        ("a", false) | ("a,", true) => Ok(Registers::RegA as i32),
+        ("b", false) | ("b,", true) => Ok(Registers::RegB as i32),
+        _ => Err(line.error(token, expected)),
+    }
+}
+
+/// One line of the program.
+#[derive(Copy,Clone,Debug)]
+pub struct Instruction {
+    pub command: Command,
+    /// The register (see [`Registers`]), or the offset for `jmp`
+    pub arg_1: i32,
+    /// The offset for `jie` and `jio`, otherwise 0
+    pub arg_2: i32,
+}
+
+
+
+/// One instruction per line, like "jio a, +18".
+pub fn read_program_from_file(input: &PuzzleInput) -> Result<Vec<Instruction>, ParseError> {
+
+    let mut program: Vec<Instruction> = Vec::new();
+    for mut line in input.tokens() {
+        let command = Command::parse(&mut line)?;
+        let (arg_1, arg_2) = match command {
+            Command::Hlf | Command::Tpl | Command::Inc => (parse_register(&mut line, false)?, 0),
+            Command::Jmp => (line.number("an offset like +7")?, 0),
+            Command::Jie | Command::Jio => {
+                let register = parse_register(&mut line, true)?;
+                (register, line.number("an offset like +7")?)
+            }
+        };
+        line.end()?;
+
+        program.push(Instruction {
+            command,
+            arg_1,
+            arg_2,
+        });
+    }
+
+    Ok(program)
+}
+
+/// The machine's registers and where it is in the program.
+#[derive(Copy,Clone,Debug)]
+pub struct ProgramState {
+    pub registers: [u32; 2],
+    pub pc: i32, // Program counter
+}
+
+fn is_even(num: i32) -> bool {
+    num % 2 == 0
+}
+
+/// Runs a single instruction and returns the state after it.
+pub fn process_instruction(mut state: ProgramState, instruction: Instruction) -> ProgramState {
+    // hlf r sets register r to half its current value, then continues with the next instruction.
+    // tpl r sets register r to triple its current value, then continues with the next instruction.
+    // inc r increments register r, adding 1 to it, then continues with the next instruction.
+    // jmp offset is a jump; it continues with the instruction offset away relative to itself.
+    // jie r, offset is like jmp, but only jumps if register r is even ("jump if even").
+    // jio r, offset is like jmp, but only jumps if register r is 1 ("jump if one", not odd).
+    let Instruction { command, arg_1, arg_2 } = instruction;
+    match command {
+        Command::Hlf => {
+            state.registers[arg_1 as usize] /= 2;
+            state.pc += 1;
+        }
+        Command::Tpl => {
+            state.registers[arg_1 as usize] *= 3;
+            state.pc += 1;
+        }
+        Command::Inc => {
+            state.registers[arg_1 as usize] += 1;
+            state.pc += 1;
+        }
+        Command::Jmp => {
+            state.pc += arg_1;
+        }
+        Command::Jie => {
+            let reg_value = state.registers[arg_1 as usize] as i32;
+            if is_even(reg_value) {
+                state.pc += arg_2;
+            } else {
+                state.pc += 1;
+            }
+        }
+        Command::Jio => {
+            let reg_value = state.registers[arg_1 as usize];
+            if reg_value == 1 {
+                state.pc += arg_2;
+            } else {
+                state.pc += 1;
+            }
+        }
+    }
+    state
+}
+
+/// Runs `program` from `state` until it jumps out of the program.
+pub fn run_program(program: &[Instruction], mut state: ProgramState) -> ProgramState {
+    // Loop until program counter indexes out of the program.
+    while state.pc >= 0 && state.pc < program.len() as i32 {
+        let instruction = program[state.pc as usize];
+        state = process_instruction(state, instruction);
+    }
+    state
+}
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Vec<Instruction>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &PuzzleInput) -> Result<Vec<Instruction>, ParseError> {
+        read_program_from_file(input)
+    }
+
+    fn part1(program: &Vec<Instruction>) -> u32 {
+        let state = ProgramState {
+            registers: [0; 2],
+            pc: 0,
+        };
+        let state = run_program(program, state);
+
+        // Check value of register B
+        debug!("Part 1: final state is {:?}", state);
+        state.registers[Registers::RegB as usize]
+    }
+
+    fn part2(program: &Vec<Instruction>) -> u32 {
+        // Now try with register A starting with value 1.
+        let state = ProgramState {
+            registers: [1, 0],
+            pc: 0,
+        };
+        let state = run_program(program, state);
+
+        // Check value of register B
+        debug!("Part 2: final state is {:?}", state);
+        state.registers[Registers::RegB as usize]
+    }
+}
+
+// Instructions that take register a from its starting value to a number
+// below 60000, which is small enough that the loop below never overflows.
+fn random_block(rng: &mut Rng, start: u32, length: usize) -> Vec<String> {
+    let mut value = start;
+    let mut block = Vec::new();
+    for idx in 0..length {
+        if idx > 0 && value > 0 && value * 3 < 60000 && rng.chance(0.4) {
+            block.push(String::from("tpl a"));
+            value *= 3;
+        } else {
+            block.push(String::from("inc a"));
+            value += 1;
+        }
+    }
+    block
+}
+
+// A Turing lock program in the real puzzle's shape: register a gets one of
+// two starting values (depending on whether it starts at 0 or 1), then b
+// counts the Collatz steps from there to 1. `size` is the length of each of
+// the two blocks that set up a.
+impl Generator for Day23 {
+    const DEFAULT_SIZE: usize = 20;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let block_1 = random_block(rng, 0, size.max(2));
+        let block_2 = random_block(rng, 1, size.max(2));
+
+        let mut program = vec![format!("jio a, +{}", block_1.len() + 2)];
+        program.extend(block_1);
+        program.push(format!("jmp +{}", block_2.len() + 1));
+        program.extend(block_2);
+        for line in &["jio a, +8", "inc b", "jie a, +4", "tpl a", "inc a", "jmp +2", "hlf a", "jmp -7"] {
+            program.push(line.to_string());
+        }
+        program.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_program() {
+        let input = PuzzleInput::from_path(concat!(env!("CARGO_MANIFEST_DIR"), "/day23_test.txt")).unwrap();
+        let program = Day23::parse(&input).unwrap();
+        let state = run_program(&program, ProgramState { registers: [0; 2], pc: 0 });
+        assert_eq!(state.registers[Registers::RegA as usize], 2);
+    }
+
+    #[test]
+    fn single_steps() {
+        let start = ProgramState { registers: [3, 0], pc: 4 };
+        let tpl = Instruction { command: Command::Tpl, arg_1: Registers::RegA as i32, arg_2: 0 };
+        let jie = Instruction { command: Command::Jie, arg_1: Registers::RegA as i32, arg_2: -3 };
+
+        let state = process_instruction(start, tpl);
+        assert_eq!((state.registers, state.pc), ([9, 0], 5));
+        // 9 is odd, so no jump
+        let state = process_instruction(state, jie);
+        assert_eq!((state.registers, state.pc), ([9, 0], 6));
+    }
+
+    // Steps from `value` down to 1, halving evens and taking odds to 3n + 1.
+    fn collatz_steps(mut value: u32) -> u32 {
+        let mut steps = 0;
+        while value != 1 {
+            value = if value.is_multiple_of(2) { value / 2 } else { 3 * value + 1 };
+            steps += 1;
+        }
+        steps
+    }
+
+    // What a block of "inc a" and "tpl a" lines leaves in a, from `start`.
+    fn run_block(lines: &[&str], start: u32) -> u32 {
+        lines.iter().fold(start, |value, line| if *line == "tpl a" { value * 3 } else { value + 1 })
+    }
+
+    #[test]
+    fn generated_programs_count_collatz_steps() {
+        for seed in 0..20 {
+            let text = aoc_common::generate::<Day23>(seed, Some(10));
+            let program = Day23::parse(&PuzzleInput::from_text("generated", &text).unwrap()).unwrap();
+            assert_eq!(program.len(), 30);
+
+            // The first line jumps past the block for a = 0 and the "jmp" after it.
+            let lines: Vec<&str> = text.lines().collect();
+            let (block_1, block_2) = (&lines[1..11], &lines[12..22]);
+            assert_eq!((lines[0], lines[11]), ("jio a, +12", "jmp +11"));
+            assert_eq!(Day23::part1(&program), collatz_steps(run_block(block_1, 0)), "seed {}", seed);
+            assert_eq!(Day23::part2(&program), collatz_steps(run_block(block_2, 1)), "seed {}", seed);
+        }
+    }
+}
@@ -0,0 +1,398 @@
+//! Day 24: It Hangs in the Balance
+//!
+//! Splitting packages into equal-weight groups with the smallest first group;
+//! [`Group`] describes one group.
+
+extern crate aoc_common;
+#[macro_use] extern crate log;
+
+use std::cmp;
+
+use aoc_common::{Generator, ParseError, PuzzleInput, Rng, Solution};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+/// The most packages there can be, since groups are kept as bitsets.
+pub const MAX_PACKAGES: usize = 32;
+
+fn make_bitset32(indices: &[usize]) -> u32 {
+    indices.iter().fold(0, |bits, &i| bits | (1 << i as u32))
+}
+
+/// Some of the packages, picked by their indices into the list of weights.
+#[derive(Debug,Copy,Clone)]
+pub struct Group {
+    /// How many packages
+    pub count: u32,
+    pub weight: u64,
+    pub qe: u64, // quantum entanglement
+    /// Bit i is set when package i is in the group
+    pub bitset: u32,
+}
+impl Group {
+    /// The group of `weights[i]` for each i in `indices`, which must all be
+    /// below [`MAX_PACKAGES`]. The quantum entanglement of a group of many
+    /// heavy packages saturates at `u64::MAX`.
+    pub fn new(weights: &[u32], indices: &[usize]) -> Group {
+        assert!(indices.iter().all(|&i| i < MAX_PACKAGES), "at most {} packages fit in a group", MAX_PACKAGES);
+        let count = indices.len() as u32;
+        let weight = indices.iter().fold(0u64, |sum, &i| sum + weights[i] as u64);
+        let qe = indices.iter().fold(1u64, |prod, &i| prod.saturating_mul(weights[i] as u64));
+        let bitset = make_bitset32(indices);
+
+        Group {
+            count,
+            weight,
+            qe,
+            bitset,
+        }
+    }
+}
+
+fn collect_indices(vals: &[bool]) -> Vec<usize> {
+    vals.iter().enumerate().filter(|&(_, val)| *val).map(|(i, _)| i).collect()
+}
+
+// For each index in the array:
+//  Add the corrosponding value to the current sum
+//  If size equal to target size:
+//      add to the list.
+//  else
+//      Recursively iterate over all indices larger than the current one
+//  Unset the current index to remove it from the active set.
+fn find_subsets(weights: &[u32],
+                start: usize,
+                target_size: u64,
+                starting_size: u64,
+                used_indices: &mut [bool],
+                results: &mut Vec<Vec<usize>>) {
+
+    for idx in start..weights.len() {
+        let used = used_indices[idx];
+        let weight = weights[idx] as u64;
+
+        if !used && starting_size + weight <= target_size {
+            used_indices[idx] = true;
+            let current_size = starting_size + weight;
+            if current_size == target_size {
+                results.push(collect_indices(used_indices));
+            } else {
+                find_subsets(weights,
+                             idx,
+                             target_size,
+                             current_size,
+                             used_indices,
+                             results);
+            }
+
+            used_indices[idx] = false;
+        }
+    }
+}
+
+// One package weight per line, for up to MAX_PACKAGES packages
+fn read_weights(input: &PuzzleInput) -> Result<Vec<u32>, ParseError> {
+    input.tokens()
+         .enumerate()
+         .map(|(idx, mut line)| {
+             let token = line.next("a package weight")?;
+             if idx == MAX_PACKAGES {
+                 return Err(line.error(token, &format!("the end of the input, after {} packages", MAX_PACKAGES)));
+             }
+             let weight = line.parse(token, "a package weight")?;
+             line.end()?;
+             Ok(weight)
+         })
+         .collect()
+}
+
+/// Every group weighing exactly `bin_size`, fewest packages first and then
+/// lowest quantum entanglement.
+pub fn find_groups(weights: &[u32], bin_size: u64) -> Vec<Group> {
+    let mut used = vec![false; weights.len()];
+    let mut results = Vec::new();
+    find_subsets(weights, 0, bin_size, 0, &mut used, &mut results);
+    debug!("found {} sets of size {}",
+           results.len(),
+           bin_size);
+
+    let mut groups: Vec<Group> = results.iter()
+                                        .map(|indices| Group::new(weights, indices))
+                                        .collect();
+    groups.sort_by_key(|a| (a.count, a.qe));    // Sort by package count, then QE
+    groups
+}
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Vec<u32>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    // Part 2 picks the lowest QE among the smallest groups, with groups that
+    // don't overlap each other.
+    const VERSION: u32 = 2;
+
+    fn parse(input: &PuzzleInput) -> Result<Vec<u32>, ParseError> {
+        read_weights(input)
+    }
+
+    fn part1(weights: &Vec<u32>) -> u64 {
+        best_qe_three_groups(weights)
+    }
+
+    fn part2(weights: &Vec<u32>) -> u64 {
+        best_qe_four_groups(weights)
+    }
+}
+
+fn best_qe_three_groups(weights: &[u32]) -> u64 {
+    let total_weight: u64 = weights.iter().map(|&weight| weight as u64).sum();
+    let bin_size_3 = total_weight / 3;
+
+    debug!("Part 1: Trying to fit {} items of total weight {} into three bins of size {} each.",
+           weights.len(),
+           total_weight,
+           bin_size_3);
+
+    let groups = find_groups(weights, bin_size_3);
+
+    // Now that the groups are sorted by package order, find the first one that
+    // has at least one other non-overlapping group (bits & bits) == 0
+    // If two groups can exist at the same time, the third exists by default
+    let mut lowest_count = u32::MAX;
+    let mut lowest_qe = u64::MAX;
+    let mut lowest_weight = u64::MAX;
+    let mut best_group = Group {
+        count: 0,
+        weight: 0,
+        qe: 0,
+        bitset: 0,
+    };
+    for group_1 in &groups {
+        if group_1.count > lowest_count {
+            break;
+        }
+
+        for group_2 in &groups {
+            let overlap = group_1.bitset & group_2.bitset;
+
+            // If compatible and the weight has not increased
+            if overlap == 0 && group_1.weight <= lowest_weight && group_1.qe < lowest_qe {
+                lowest_count = cmp::min(lowest_count, group_1.count);
+                lowest_weight = cmp::min(lowest_weight, group_1.weight);
+                lowest_qe = cmp::min(lowest_qe, group_1.qe);
+                best_group = *group_1;
+            }
+        }
+    }
+
+    debug!("Part 1: found best group_1: {:?}", best_group);
+    best_group.qe
+}
+
+// Create four equal weight groups instead of three.
+fn best_qe_four_groups(weights: &[u32]) -> u64 {
+    let total_weight: u64 = weights.iter().map(|&weight| weight as u64).sum();
+
+    let bin_size_4 = total_weight / 4;
+    debug!("Part 2: Trying to fit {} items of total weight {} into four bins of size {} each.",
+           weights.len(),
+           total_weight,
+           bin_size_4);
+
+    let groups = find_groups(weights, bin_size_4);
+
+    debug!("sorted. Now filtering...");
+
+    // Now that the groups are sorted by package count and QE, find the first one
+    // that has two other groups that don't overlap it or each other.
+    // If three groups can exist at the same time, the fourth exists by default
+    //
+    // Note: this is extremely slow without breaking on the first result. Should find a better way.
+    let mut best_group = Group {
+        count: 0,
+        weight: 0,
+        qe: 0,
+        bitset: 0,
+    };
+    'outer: for group_1 in &groups {
+        for group_2 in &groups {
+            if group_1.bitset & group_2.bitset == 0 {
+                for group_3 in &groups {
+                    if (group_1.bitset | group_2.bitset) & group_3.bitset == 0 {
+                        best_group = *group_1;
+                        break 'outer;
+                    }
+                }
+            }
+        }
+        trace!("group_1.count {}", group_1.count);
+    }
+
+    debug!("Part 2: found best group_1: {:?}", best_group);
+    best_group.qe
+}
+
+// Twelve different weights in a 3x4 grid, where every row weighs `column` * 4 / 3
+// and every column weighs `column`. The rows split the packages into three
+// equal groups and the columns into four.
+fn random_grid(rng: &mut Rng, column: u32) -> Option<Vec<u32>> {
+    let row = column as i64 * 4 / 3;
+    let mut grid = [[0i64; 4]; 3];
+    for cells in grid.iter_mut().take(2) {
+        for cell in cells.iter_mut().take(3) {
+            *cell = 1 + rng.below(column as usize / 2) as i64;
+        }
+        cells[3] = row - cells[..3].iter().sum::<i64>();
+    }
+    let [first, second, last] = &mut grid;
+    for ((cell, above), top) in last.iter_mut().zip(second.iter()).zip(first.iter()) {
+        *cell = column as i64 - top - above;
+    }
+
+    let weights: Vec<u32> = grid.iter().flatten().map(|&cell| cell as u32).collect();
+    let all_different = weights.iter().enumerate().all(|(idx, weight)| !weights[idx + 1..].contains(weight));
+    if grid.iter().flatten().all(|&cell| cell > 0) && all_different {
+        Some(weights)
+    } else {
+        None
+    }
+}
+
+// `size` different package weights (12 to MAX_PACKAGES of them) that can be split into
+// both three and four groups of equal weight.
+impl Generator for Day24 {
+    const DEFAULT_SIZE: usize = 28;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.clamp(12, MAX_PACKAGES);
+        // About what the real inputs weigh in total
+        let column = 3 * (size * size / 6).max(4) as u32;
+
+        let weights = 'retry: loop {
+            let mut weights = match random_grid(rng, column) {
+                Some(weights) => weights,
+                None => continue,
+            };
+
+            // Split packages in two until there are enough. Each half stays in
+            // the same cell of the grid, so the groups still balance.
+            while weights.len() < size {
+                let idx = rng.below(weights.len());
+                let weight = weights[idx];
+                if weight < 3 {
+                    continue 'retry;
+                }
+                let part = 1 + rng.below(weight as usize - 1) as u32;
+                if part * 2 == weight || weights.contains(&part) || weights.contains(&(weight - part)) {
+                    continue 'retry;
+                }
+                weights[idx] = part;
+                weights.push(weight - part);
+            }
+            break weights;
+        };
+
+        let mut weights = weights;
+        weights.sort();
+        let lines: Vec<String> = weights.iter().map(|weight| weight.to_string()).collect();
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WEIGHTS: [u32; 10] = [1, 2, 3, 4, 5, 7, 8, 9, 10, 11];
+
+    #[test]
+    fn too_many_packages() {
+        let text: Vec<String> = (1..=MAX_PACKAGES + 1).map(|weight| weight.to_string()).collect();
+        let error = Day24::parse(&PuzzleInput::from_text("packages", &text.join("\n")).unwrap()).unwrap_err();
+        assert_eq!((error.line, error.column), (MAX_PACKAGES + 1, 1));
+
+        let fits = Day24::parse(&PuzzleInput::from_text("packages", &text[..MAX_PACKAGES].join("\n")).unwrap());
+        assert_eq!(fits.unwrap().len(), MAX_PACKAGES);
+    }
+
+    #[test]
+    fn heavy_packages() {
+        // Each list weighs more than a u32 can hold.
+        assert_eq!(Day24::part1(&vec![4_000_000_000; 6]), 16_000_000_000_000_000_000);
+        assert_eq!(Day24::part2(&vec![3_000_000_000; 8]), 9_000_000_000_000_000_000);
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day24::part1(&WEIGHTS.to_vec()), 99);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day24::part2(&WEIGHTS.to_vec()), 44);
+    }
+
+    #[test]
+    fn groups_of_packages() {
+        let weights = [1, 2, 3, 4, 5, 7, 8, 9, 10, 11];
+        let group = Group::new(&weights, &[3, 9]);
+        assert_eq!((group.count, group.weight, group.qe, group.bitset), (2, 15, 44, 0b10_0000_1000));
+
+        let groups = find_groups(&weights, 20);
+        assert_eq!((groups[0].count, groups[0].weight), (2, 20));
+        assert!(groups.windows(2).all(|pair| pair[0].count <= pair[1].count));
+    }
+
+    // Bitsets of every group of packages weighing `total` / `groups`.
+    fn groups_weighing(weights: &[u32], groups: u64) -> Vec<u32> {
+        let target = weights.iter().map(|&weight| weight as u64).sum::<u64>() / groups;
+        (0u32..1 << weights.len())
+            .filter(|&bits| weights.iter().enumerate().filter(|&(idx, _)| bits & 1 << idx != 0).map(|(_, &weight)| weight as u64).sum::<u64>() == target)
+            .collect()
+    }
+
+    // The lowest quantum entanglement of a first group with the fewest
+    // packages, out of every first group whose remainder splits into
+    // `groups` - 1 more groups of the same weight.
+    fn best_split(weights: &[u32], groups: u64) -> Option<u64> {
+        let all = groups_weighing(weights, groups);
+        let everything = (1u32 << weights.len()) - 1;
+        let splits = |first: u32| -> bool {
+            let rest = everything & !first;
+            match groups {
+                3 => all.iter().any(|&second| second & first == 0 && all.contains(&(rest & !second))),
+                _ => all.iter().any(|&second| {
+                    second & first == 0 && all.iter().any(|&third| third & (first | second) == 0 && all.contains(&(rest & !second & !third)))
+                }),
+            }
+        };
+        all.iter()
+           .filter(|&&first| splits(first))
+           .map(|&first| {
+               let qe = weights.iter().enumerate().filter(|&(idx, _)| first & 1 << idx != 0).map(|(_, &weight)| weight as u64).product::<u64>();
+               (first.count_ones(), qe)
+           })
+           .min()
+           .map(|(_, qe)| qe)
+    }
+
+    #[test]
+    fn generated_weights_split_three_and_four_ways() {
+        for seed in 0..10 {
+            let text = aoc_common::generate::<Day24>(seed, Some(12));
+            let weights = Day24::parse(&PuzzleInput::from_text("generated", &text).unwrap()).unwrap();
+            assert_eq!(weights.len(), 12);
+            assert_eq!(weights.iter().sum::<u32>() % 12, 0);
+
+            let (three, four) = (best_split(&weights, 3), best_split(&weights, 4));
+            assert!(three.is_some() && four.is_some(), "seed {}", seed);
+            assert_eq!(Some(Day24::part1(&weights)), three, "seed {}", seed);
+            assert_eq!(Some(Day24::part2(&weights)), four, "seed {}", seed);
+        }
+    }
+}
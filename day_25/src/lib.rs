@@ -0,0 +1,195 @@
+//! Day 25: Let It Snow
+//!
+//! The code at a row and column of the diagonal code grid.
+
+extern crate aoc_common;
+#[macro_use] extern crate log;
+
+use std::ops::RangeInclusive;
+
+use aoc_common::{Differential, Generator, ParseError, PuzzleInput, Rng, Solution};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+// Each code is generated by taking the previous one, multiplying it by 252533, and
+//  then keeping the remainder from dividing that value by 33554393.
+//
+fn gen_next_code(prev: u64) -> u64 {
+    (prev * 252533) % 33554393
+}
+
+
+// Finds the number following `label` in the puzzle key, e.g. "row 3010,".
+fn read_coordinate(input: &PuzzleInput, label: &str) -> Result<usize, ParseError> {
+    let expected = format!("a {} number", label);
+    for mut line in input.tokens() {
+        while let Ok(word) = line.next("") {
+            if word.text != label {
+                continue;
+            }
+
+            let token = line.next(&expected)?;
+            let number = line.parse::<usize>(token.trim_end(&[',', '.']), &expected)?;
+            if number == 0 {
+                return Err(line.error(token, &format!("a {} number from 1 up", label)));
+            }
+            return Ok(number);
+        }
+    }
+    Err(input.last_line().error_at_end(&format!("'{}' followed by a number", label)))
+}
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = (usize, usize);
+    type Part1 = u64;
+    type Part2 = &'static str;
+
+    // Puzzle key:
+    // To continue, please consult the code grid in the manual.  Enter the code at row 3010, column 3019.
+    fn parse(input: &PuzzleInput) -> Result<(usize, usize), ParseError> {
+        let row = read_coordinate(input, "row")?;
+        let column = read_coordinate(input, "column")?;
+        Ok((row, column))
+    }
+
+    fn part1(&(target_row, target_column): &(usize, usize)) -> u64 {
+        code_at(target_row, target_column)
+    }
+
+    // Day 25 only has one puzzle; the second star is free.
+    fn part2(_: &(usize, usize)) -> &'static str {
+        "Merry Christmas!"
+    }
+}
+
+fn find_code(target_row: usize, target_column: usize) -> u64 {
+
+    // The codes are generated in up-to-the-right diagnals, so all we need to store is
+    // the first code in each row and the previously generated code.
+
+    // The first code is 20151125.
+    let mut first_column = Vec::with_capacity(target_row + target_column + 1);
+    first_column.push(20151125);
+
+    // Table starts at (1,1)
+    let mut row = 1;
+    let mut col = 1;
+    let mut code = first_column[0];
+
+    // Generate codes by diagonal until we reach the target cell
+    while row < target_row || col < target_column {
+
+        if row == 1 {
+            row = first_column.len() + 1;
+            col = 1;
+        } else {
+            row -= 1;
+            col += 1;
+        }
+
+        code = gen_next_code(code);
+
+        if col == 1 {
+            first_column.push(code);
+        }
+
+        // if col == 1 {
+        // debug!("Code at ({}, {}) is {}", row, col, code);
+        // }
+    }
+
+    debug!("Code at ({}, {}) is {}", row, col, code);
+    code
+}
+
+// How many codes come before (row, column) in the order they're generated.
+fn code_index(row: usize, column: usize) -> u64 {
+    let diagonal = (row + column - 2) as u64;
+    diagonal * (diagonal + 1) / 2 + column as u64 - 1
+}
+
+// Every code is the first one times 252533^index, so instead of walking the
+// diagonals we can square-and-multiply our way there.
+fn code_at(row: usize, column: usize) -> u64 {
+    let mut code = 20151125;
+    let mut factor = 252533;
+    let mut exponent = code_index(row, column);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            code = (code * factor) % 33554393;
+        }
+        factor = (factor * factor) % 33554393;
+        exponent >>= 1;
+    }
+    code
+}
+
+// A code grid position with the row and column each up to `size`.
+impl Generator for Day25 {
+    const DEFAULT_SIZE: usize = 3000;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(1);
+        format!("To continue, please consult the code grid in the manual.  Enter the code at row {}, column {}.",
+                1 + rng.below(size),
+                1 + rng.below(size))
+    }
+}
+
+// The diagonal walk is slow but follows the puzzle text step by step.
+impl Differential for Day25 {
+    type Answer = u64;
+
+    const SIZES: RangeInclusive<usize> = 1..=60;
+
+    fn reference(&(row, column): &(usize, usize)) -> u64 {
+        find_code(row, column)
+    }
+
+    fn fast(&(row, column): &(usize, usize)) -> u64 {
+        code_at(row, column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The top-left corner of the code grid from the puzzle text
+    const CODES: [[u64; 6]; 6] = [
+        [20151125, 18749137, 17289845, 30943339, 10071777, 33511524],
+        [31916031, 21629792, 16929656,  7726640, 15514188,  4041754],
+        [16080970,  8057251,  1601130,  7981243, 11661866, 16474243],
+        [24592653, 32451966, 21345942,  9380097, 10600672, 31527494],
+        [   77061, 17552253, 28094349,  6899651,  9250759, 31663883],
+        [33071741,  6796745, 25397450, 24659492,  1534922, 27995004],
+    ];
+
+    #[test]
+    fn example_grid() {
+        for (row_idx, row) in CODES.iter().enumerate() {
+            for (col_idx, &code) in row.iter().enumerate() {
+                assert_eq!(find_code(row_idx + 1, col_idx + 1), code, "row {}, column {}", row_idx + 1, col_idx + 1);
+                assert_eq!(code_at(row_idx + 1, col_idx + 1), code, "row {}, column {}", row_idx + 1, col_idx + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn reads_row_and_column() {
+        let key = "Enter the code at row 3010, column 3019.";
+        let input = PuzzleInput::from_text("example", key).unwrap();
+        assert_eq!(Day25::parse(&input).unwrap(), (3010, 3019));
+    }
+
+    #[test]
+    fn walk_matches_exponent() {
+        if let Err(mismatch) = aoc_common::differential::check::<Day25>(0..20) {
+            panic!("{}", mismatch);
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Santa and any number of helpers delivering presents from one set of moves.
+//!
+//! Every agent starts at (0, 0) and leaves a present there, then follows the
+//! moves a [`Dispatch`] policy hands it, leaving a present at each house it
+//! moves to. Santa alone is part 1, and Santa taking turns with Robo-Santa is
+//! part 2.
+
+use std::collections::HashSet;
+
+use aoc_common::{Point, SparseGrid};
+
+/// How the moves are shared out between the agents.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Dispatch {
+	/// `agents` agents take one move each in turn.
+	RoundRobin { agents: usize },
+	/// Agent i takes `weights[i]` moves in a row, then the next agent has its
+	/// turn.
+	Weighted { weights: Vec<usize> },
+	/// Each line of moves belongs to a different agent.
+	PerAgent,
+}
+
+// The house one move away. Rows grow downwards, so north is y - 1.
+fn step(house: Point, direction: char) -> Option<Point> {
+	match direction {
+		'^' => Some(house.offset(0, -1)),
+		'v' => Some(house.offset(0, 1)),
+		'<' => Some(house.offset(-1, 0)),
+		'>' => Some(house.offset(1, 0)),
+		_ => None,
+	}
+}
+
+impl Dispatch {
+	/// Each agent's moves, in order. Anything that isn't a move is skipped.
+	///
+	/// Panics if there are no agents, or an agent has a weight of 0.
+	pub fn split(&self, moves: &str) -> Vec<Vec<char>> {
+		let is_move = |c: &char| "^v<>".contains(*c);
+		let weights = match *self {
+			Dispatch::RoundRobin { agents } => vec![1; agents],
+			Dispatch::Weighted { ref weights } => weights.clone(),
+			Dispatch::PerAgent => return moves.lines().map(|line| line.chars().filter(is_move).collect()).collect(),
+		};
+		assert!(!weights.is_empty() && !weights.contains(&0), "every agent needs a turn: {:?}", self);
+
+		let turns = weights.iter().enumerate().flat_map(|(agent, &weight)| std::iter::repeat_n(agent, weight)).cycle();
+		let mut streams = vec![Vec::new(); weights.len()];
+		for (agent, direction) in turns.zip(moves.chars().filter(is_move)) {
+			streams[agent].push(direction);
+		}
+		streams
+	}
+}
+
+/// Where everyone went.
+#[derive(Clone, Debug)]
+pub struct Delivery {
+	/// Presents per house, from every agent.
+	pub houses: SparseGrid<u32>,
+	/// Each agent's houses in order, from (0, 0).
+	pub trails: Vec<Vec<Point>>,
+	// How many different agents visited each house
+	agents_per_house: SparseGrid<u32>,
+}
+
+impl Delivery {
+	/// Houses that got at least one present.
+	pub fn unique_houses(&self) -> usize {
+		self.houses.len()
+	}
+
+	/// Houses visited by more than one agent.
+	pub fn shared_houses(&self) -> usize {
+		self.agents_per_house.iter().filter(|&(_, &agents)| agents > 1).count()
+	}
+
+	/// How many different agents visited `house`.
+	pub fn agents_at(&self, house: Point) -> u32 {
+		self.agents_per_house.get(house).cloned().unwrap_or(0)
+	}
+}
+
+/// Delivers presents along `moves`, shared out by `dispatch`.
+pub fn deliver(moves: &str, dispatch: &Dispatch) -> Delivery {
+	let mut houses = SparseGrid::new();
+	let mut agents_per_house = SparseGrid::new();
+	let mut trails = Vec::new();
+	for stream in dispatch.split(moves) {
+		let mut house = Point::new(0, 0);
+		let mut trail = vec![house];
+		trail.extend(stream.iter().filter_map(|&direction| {
+			house = step(house, direction)?;
+			Some(house)
+		}));
+
+		for &house in &trail {
+			*houses.entry(house).or_insert(0) += 1;
+		}
+		let visited: HashSet<Point> = trail.iter().cloned().collect();
+		for house in visited {
+			*agents_per_house.entry(house).or_insert(0) += 1;
+		}
+		trails.push(trail);
+	}
+
+	Delivery { houses, trails, agents_per_house }
+}
@@ -0,0 +1,148 @@
+//! Day 3: Perfectly Spherical Houses in a Vacuum
+//!
+//! Santa (and Robo-Santa) deliver presents on an infinite grid of houses;
+//! [`deliver`] follows any number of agents sharing the moves out by a
+//! [`Dispatch`] policy, counting presents per house. [`report`] summarises
+//! and draws the routes.
+
+extern crate aoc_common;
+
+pub mod delivery;
+pub mod report;
+
+use aoc_common::{Generator, ParseError, PuzzleInput, Rng, Solution};
+
+pub use delivery::{deliver, Delivery, Dispatch};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day3.txt");
+
+/// Santa alone, as in part 1.
+pub const SANTA: Dispatch = Dispatch::RoundRobin { agents: 1 };
+
+/// Santa and Robo-Santa taking turns, as in part 2.
+pub const SANTA_AND_ROBOT: Dispatch = Dispatch::RoundRobin { agents: 2 };
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day3;
+
+impl Solution for Day3 {
+	type Input = String;
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &PuzzleInput) -> Result<String, ParseError> {
+		for line in input.tokens() {
+			for (offset, c) in line.text().char_indices() {
+				if !"^v<>".contains(c) {
+					return Err(line.error_at(offset, 1, "one of '^', 'v', '<' or '>'", &format!("'{}'", c)));
+				}
+			}
+		}
+		Ok(input.text().to_string())
+	}
+
+	fn part1(input_string: &String) -> usize {
+		deliver(input_string, &SANTA).unique_houses()
+	}
+
+	fn part2(input_string: &String) -> usize {
+		deliver(input_string, &SANTA_AND_ROBOT).unique_houses()
+	}
+}
+
+// `size` moves in random directions.
+impl Generator for Day3 {
+	const DEFAULT_SIZE: usize = 8192;
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		(0..size).map(|_| *rng.choose(&['^', 'v', '<', '>'])).collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use aoc_common::Point;
+
+	fn example(text: &str) -> String {
+		Day3::parse(&PuzzleInput::from_text("example", text).unwrap()).unwrap()
+	}
+
+	#[test]
+	fn part1_examples() {
+		assert_eq!(Day3::part1(&example(">")), 2);
+		assert_eq!(Day3::part1(&example("^>v<")), 4);
+		assert_eq!(Day3::part1(&example("^v^v^v^v^v")), 2);
+	}
+
+	#[test]
+	fn part2_examples() {
+		assert_eq!(Day3::part2(&example("^v")), 3);
+		assert_eq!(Day3::part2(&example("^>v<")), 3);
+		assert_eq!(Day3::part2(&example("^v^v^v^v^v")), 11);
+	}
+
+	#[test]
+	fn presents_per_house() {
+		let houses = deliver("^v^v", &SANTA).houses;
+		assert_eq!(houses.get(Point::new(0, 0)), Some(&3));
+		assert_eq!(houses.get(Point::new(0, -1)), Some(&2));
+		assert_eq!(houses.bounds(), Some((Point::new(0, -1), Point::new(0, 0))));
+
+		let (dense, origin) = deliver("^>v<", &SANTA_AND_ROBOT).houses.to_dense(0);
+		assert_eq!((dense.width(), dense.height(), origin), (2, 2, Point::new(0, -1)));
+		assert_eq!(dense.iter().sum::<u32>(), 6);
+	}
+
+	#[test]
+	fn dispatch_policies() {
+		let moves = ">>^^<v";
+		assert_eq!(SANTA_AND_ROBOT.split(moves), vec![vec!['>', '^', '<'], vec!['>', '^', 'v']]);
+		assert_eq!(Dispatch::Weighted { weights: vec![2, 1] }.split(moves),
+		           vec![vec!['>', '>', '^', '<'], vec!['^', 'v']]);
+		assert_eq!(Dispatch::PerAgent.split(">>\n\n<^"), vec![vec!['>', '>'], vec![], vec!['<', '^']]);
+
+		// Weights of one are round robin, and a weight bigger than the moves is
+		// one agent doing everything
+		let text = aoc_common::generate::<Day3>(4, Some(300));
+		let weighted = deliver(&text, &Dispatch::Weighted { weights: vec![1, 1] });
+		assert_eq!(weighted.unique_houses(), Day3::part2(&text));
+		assert_eq!(deliver(&text, &Dispatch::Weighted { weights: vec![1000, 5] }).unique_houses(), Day3::part1(&text));
+	}
+
+	#[test]
+	fn agents_and_trails() {
+		let delivery = deliver(">>\n<<\n>^", &Dispatch::PerAgent);
+		assert_eq!(delivery.trails,
+		           vec![vec![Point::new(0, 0), Point::new(1, 0), Point::new(2, 0)],
+		                vec![Point::new(0, 0), Point::new(-1, 0), Point::new(-2, 0)],
+		                vec![Point::new(0, 0), Point::new(1, 0), Point::new(1, -1)]]);
+		assert_eq!(delivery.unique_houses(), 6);
+		// (0, 0) by everyone and (1, 0) by the first and third
+		assert_eq!(delivery.shared_houses(), 2);
+		assert_eq!(delivery.agents_at(Point::new(0, 0)), 3);
+		assert_eq!(delivery.houses.get(Point::new(0, 0)), Some(&3));
+		assert_eq!(delivery.agents_at(Point::new(5, 5)), 0);
+
+		// Agents visiting a house twice still count once
+		let delivery = deliver("^v^v", &SANTA);
+		assert_eq!((delivery.shared_houses(), delivery.agents_at(Point::new(0, 0))), (0, 1));
+	}
+
+	#[test]
+	fn route_statistics() {
+		// Santa goes round a square and back up, Robo-Santa just right
+		let delivery = deliver("^>v<^\n>", &Dispatch::PerAgent);
+		let stats = report::stats(&delivery, 2);
+		assert_eq!(stats.bounds, Some((Point::new(0, -1), Point::new(1, 0))));
+		assert_eq!(stats.busiest, vec![(Point::new(0, 0), 3), (Point::new(0, -1), 2)]);
+		assert_eq!(stats.histogram.into_iter().collect::<Vec<_>>(), vec![(1, 1), (2, 2), (3, 1)]);
+		assert_eq!(stats.self_intersections, vec![2, 0]);
+
+		assert_eq!(report::houses_csv(&delivery), "x,y,count\n0,-1,2\n1,-1,1\n0,0,3\n1,0,2\n");
+		let heatmap = report::heatmap(&delivery);
+		assert!(heatmap.starts_with(b"P6\n2 2\n255\n"));
+		assert_eq!(heatmap.len(), 11 + 2 * 2 * 3);
+	}
+}
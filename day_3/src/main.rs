@@ -1,59 +1,148 @@
- 
-use std::error::Error;
-use std::fs::File;
-use std::io;
-use std::io::prelude::*;
-use std::collections::HashMap;
-
-#[derive(PartialEq, Eq, Hash)]
-struct Point {
-	x: i32,
-	y: i32,
+extern crate aoc_common;
+extern crate day_3;
+
+use std::env;
+use std::io::{self, Write};
+use std::process;
+
+use aoc_common::{LoadError, PuzzleInput, Solution, Tokens};
+use day_3::{report, Day3, Delivery, Dispatch};
+
+// Prints the answers, or delivers with other agents:
+//
+//     day_3 --agents 3                  three agents taking turns
+//     day_3 --weights 3,1               one agent takes three moves, the next one
+//     day_3 --per-agent --input FILE    each line of FILE is one agent's moves
+//
+// and instead of a summary of the agents, print:
+//
+//     --trails           every agent's houses in order, as CSV
+//     --report           the area covered, the busiest houses, how many houses
+//                        got each number of presents, and where routes cross
+//     --csv              presents per house, as CSV
+//     --heatmap          presents per house as a PPM image, e.g. > houses.ppm
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Output {
+	Summary,
+	Trails,
+	Report,
+	Csv,
+	Heatmap,
+}
+
+struct Options {
+	dispatch: Option<Dispatch>,
+	input: Option<String>,
+	output: Output,
+}
+
+// Reads the command line as if it were a line of input, for its diagnostics.
+fn read_args(args: &str) -> Result<Options, LoadError> {
+	let mut line = Tokens::new("command line", 1, args);
+	let mut options = Options { dispatch: None, input: None, output: Output::Summary };
+	let expected = "--agents, --weights, --per-agent, --input, --trails, --report, --csv or --heatmap";
+	while let Ok(token) = line.next(expected) {
+		match token.text {
+			"--agents" => {
+				let agents = line.next("how many agents")?;
+				match line.parse(agents, "how many agents")? {
+					0 => return Err(line.error(agents, "at least one agent").into()),
+					agents => options.dispatch = Some(Dispatch::RoundRobin { agents }),
+				}
+			}
+			"--weights" => {
+				let list = line.next("moves per turn for each agent, like 3,1")?;
+				let weights = list.split(',')
+					.into_iter()
+					.map(|weight| match line.parse(weight, "a number of moves")? {
+						0 => Err(line.error(weight, "at least one move")),
+						moves => Ok(moves),
+					})
+					.collect::<Result<Vec<usize>, _>>()?;
+				options.dispatch = Some(Dispatch::Weighted { weights });
+			}
+			"--per-agent" => options.dispatch = Some(Dispatch::PerAgent),
+			"--input" => options.input = Some(line.next("an input file")?.text.to_string()),
+			"--trails" => options.output = Output::Trails,
+			"--report" => options.output = Output::Report,
+			"--csv" => options.output = Output::Csv,
+			"--heatmap" => options.output = Output::Heatmap,
+			_ => return Err(line.error(token, expected).into()),
+		}
+	}
+	Ok(options)
+}
+
+fn print_summary(delivery: &Delivery) {
+	println!("{} houses got presents, {} of them from more than one agent",
+		delivery.unique_houses(), delivery.shared_houses());
+	for (agent, trail) in delivery.trails.iter().enumerate() {
+		let end = trail.last().expect("trails start at (0, 0)");
+		let houses = trail.iter().collect::<std::collections::HashSet<_>>().len();
+		println!("agent {}: {} moves, {} houses, ending at ({}, {})", agent + 1, trail.len() - 1, houses, end.x, end.y);
+	}
+}
+
+fn print_trails(delivery: &Delivery) {
+	println!("agent,step,x,y");
+	for (agent, trail) in delivery.trails.iter().enumerate() {
+		for (step, house) in trail.iter().enumerate() {
+			println!("{},{},{},{}", agent + 1, step, house.x, house.y);
+		}
+	}
 }
 
-fn read_file(filename: String) -> Result<String, io::Error> {
-	let mut f = try!(File::open(filename));
-	let mut s = String::new();
-	try!(f.read_to_string(&mut s));
-	return Ok(s)
+fn print_report(delivery: &Delivery) {
+	let stats = report::stats(delivery, 5);
+	if let Some((top_left, bottom_right)) = stats.bounds {
+		println!("{} houses from ({}, {}) to ({}, {})",
+			delivery.unique_houses(), top_left.x, top_left.y, bottom_right.x, bottom_right.y);
+	}
+
+	println!("\nbusiest houses:");
+	for (house, presents) in &stats.busiest {
+		println!("  ({}, {}): {} presents", house.x, house.y, presents);
+	}
+
+	println!("\npresents  houses");
+	for (presents, houses) in &stats.histogram {
+		println!("{:>8}  {:>6}", presents, houses);
+	}
+
+	println!();
+	for (agent, crossings) in stats.self_intersections.iter().enumerate() {
+		println!("agent {} went back to a house it had been to {} times", agent + 1, crossings);
+	}
 }
- 
-fn main() {
- 
-	let input = read_file("day3.txt".to_string());
-	assert!(input.is_ok());
-	let input_string = input.unwrap();
-	let chars = input_string.chars();
-	
-	let mut visited = HashMap::new();
-	visited.insert(Point{x:0,y:0}, 0);
-	
-	let mut active_point = Point{x:0, y:0};
-	let mut inactive_point = Point{x:0, y:0};
-	
-	
-	for c in chars {
-		match c {
-			'^' => active_point.y += 1,
-			'v' => active_point.y -= 1,
-			'<' => active_point.x -= 1,
-			'>' => active_point.x += 1,
-			_ => { /* ignore everything else */ }
+
+fn main() -> Result<(), LoadError> {
+	let options = read_args(&env::args().skip(1).collect::<Vec<String>>().join(" "))?;
+	let path = options.input.as_deref().unwrap_or(day_3::INPUT);
+	let input = Day3::parse(&PuzzleInput::from_path(path)?)?;
+
+	let dispatch = match options.dispatch {
+		Some(dispatch) => dispatch,
+		None if options.output == Output::Summary => {
+			println!("day 3 part 1: {}", Day3::part1(&input));
+			println!("day 3 part 2: {}", Day3::part2(&input));
+			return Ok(());
+		}
+		None => day_3::SANTA,
+	};
+
+	let delivery = day_3::deliver(&input, &dispatch);
+	match options.output {
+		Output::Summary => print_summary(&delivery),
+		Output::Trails => print_trails(&delivery),
+		Output::Report => print_report(&delivery),
+		Output::Csv => print!("{}", report::houses_csv(&delivery)),
+		Output::Heatmap => {
+			if let Err(error) = io::stdout().write_all(&report::heatmap(&delivery)) {
+				eprintln!("error: couldn't write the heatmap: {}", error);
+				process::exit(1);
+			}
 		}
-		
-		// Get the count at the current point, inserting empty value if needed
-		// and incrementing it.
-		let key = Point{.. active_point};
-		*visited.entry(key).or_insert(0) += 1;
-		
-		std::mem::swap(&mut active_point, &mut inactive_point);
-		
-	}
-	
-	let mut num_visited = 0;
-	for (_,_) in &visited {
-		num_visited += 1;
-	}
-	
-	println!("Number of houses visited: {}", num_visited);
-}
\ No newline at end of file
+	}
+
+	Ok(())
+}
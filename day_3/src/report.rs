@@ -0,0 +1,65 @@
+//! Statistics and pictures of a delivery, for looking at the routes.
+
+use std::collections::{BTreeMap, HashSet};
+
+use aoc_common::{image, Point};
+
+use crate::Delivery;
+
+/// What a delivery looked like.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Stats {
+	/// The top left and bottom right houses of the smallest rectangle around
+	/// every house that got a present.
+	pub bounds: Option<(Point, Point)>,
+	/// Houses with the most presents and how many they got, most first. Ties
+	/// are in reading order.
+	pub busiest: Vec<(Point, u32)>,
+	/// How many houses got each number of presents.
+	pub histogram: BTreeMap<u32, usize>,
+	/// For each agent, how many of its moves took it back to a house it had
+	/// already been to, where its route crosses or doubles back on itself.
+	pub self_intersections: Vec<usize>,
+}
+
+/// Statistics for `delivery`, listing the `busiest` houses.
+pub fn stats(delivery: &Delivery, busiest: usize) -> Stats {
+	let mut houses: Vec<(Point, u32)> = delivery.houses.iter().map(|(house, &presents)| (house, presents)).collect();
+	houses.sort_by_key(|&(house, presents)| (std::cmp::Reverse(presents), house.y, house.x));
+
+	let mut histogram = BTreeMap::new();
+	for &(_, presents) in &houses {
+		*histogram.entry(presents).or_insert(0) += 1;
+	}
+
+	let self_intersections = delivery.trails
+		.iter()
+		.map(|trail| {
+			let mut seen = HashSet::new();
+			trail.iter().filter(|&&house| !seen.insert(house)).count()
+		})
+		.collect();
+
+	houses.truncate(busiest);
+	Stats { bounds: delivery.houses.bounds(), busiest: houses, histogram, self_intersections }
+}
+
+/// The houses as a PPM image, one pixel per house, from black for none
+/// through red to yellow-white for the most presents.
+pub fn heatmap(delivery: &Delivery) -> Vec<u8> {
+	let (grid, _) = delivery.houses.to_dense(0);
+	let most = grid.iter().cloned().max().unwrap_or(0) as u64;
+	image::ppm(&grid, |&presents| image::heat(presents as u64, most))
+}
+
+/// Every house that got a present, in reading order, as CSV.
+pub fn houses_csv(delivery: &Delivery) -> String {
+	let mut houses: Vec<(Point, u32)> = delivery.houses.iter().map(|(house, &presents)| (house, presents)).collect();
+	houses.sort_by_key(|&(house, _)| (house.y, house.x));
+
+	let mut csv = String::from("x,y,count\n");
+	for (house, presents) in houses {
+		csv.push_str(&format!("{},{},{}\n", house.x, house.y, presents));
+	}
+	csv
+}
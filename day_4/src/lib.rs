@@ -0,0 +1,130 @@
+//! Day 4: The Ideal Stocking Stuffer
+//!
+//! Mining AdventCoins: MD5 hashes of the secret key with leading zeros.
+//! [`miner`] does the searching, on as many threads as there are CPUs.
+
+extern crate aoc_common;
+#[macro_use] extern crate log;
+extern crate crypto;
+
+use crypto::md5::Md5;
+use crypto::digest::Digest;
+
+use aoc_common::{Generator, ParseError, PuzzleInput, Rng, Solution};
+
+pub mod miner;
+
+pub use miner::Miner;
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day4.txt");
+
+/// The MD5 hash of `input` as lowercase hex.
+pub fn md5(input: &str) -> String {
+	let mut digest = Md5::new();
+	digest.input(input.as_bytes());
+
+	digest.result_str()
+}
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day4;
+
+impl Solution for Day4 {
+	type Input = String;
+	type Part1 = u64;
+	type Part2 = u64;
+
+	// The input is the secret key, like "abcdef"
+	fn parse(input: &PuzzleInput) -> Result<String, ParseError> {
+		let mut line = input.single_line()?;
+		let token = line.next("the secret key")?;
+		line.end()?;
+		Ok(token.text.to_string())
+	}
+
+	// Search for hash with "00000" prefix
+	fn part1(input_prefix: &String) -> u64 {
+		Miner::default().mine(input_prefix, 5).expect("the search has no upper bound")
+	}
+
+	// Search for hash with "000000" prefix
+	fn part2(input_prefix: &String) -> u64 {
+		Miner::default().mine(input_prefix, 6).expect("the search has no upper bound")
+	}
+}
+
+// A secret key of `size` lowercase letters.
+impl Generator for Day4 {
+	const DEFAULT_SIZE: usize = 8;
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		(0..size.max(1)).map(|_| (b'a' + rng.below(26) as u8) as char).collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn md5_of_example() {
+		assert_eq!(md5("abcdef609043"), "000001dbbfa3a5c83a2d506429c7b00e");
+	}
+
+	#[test]
+	fn part1_examples() {
+		assert_eq!(Day4::part1(&"abcdef".to_string()), 609043);
+		assert_eq!(Day4::part1(&"pqrstuv".to_string()), 1048970);
+	}
+
+	#[test]
+	fn leading_zeros() {
+		let hash = [0x00, 0x00, 0x0f, 0xff, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+		assert!(miner::has_zeros(&hash, 0));
+		assert!(miner::has_zeros(&hash, 4));
+		assert!(miner::has_zeros(&hash, 5));
+		assert!(!miner::has_zeros(&hash, 6));
+		assert!(miner::has_zeros(&[0; 16], miner::MAX_DIFFICULTY));
+	}
+
+	#[test]
+	fn lowest_nonce_whatever_the_threads() {
+		// The first nonce with three zeros, found by hashing one at a time
+		let lowest = (1..).find(|nonce| md5(&format!("abcdef{}", nonce)).starts_with("000")).unwrap();
+		for threads in 1..=4 {
+			let miner = Miner { threads, limit: u64::MAX };
+			assert_eq!(miner.mine("abcdef", 3), Some(lowest));
+		}
+	}
+
+	#[test]
+	fn search_stops_at_the_limit() {
+		let miner = Miner { threads: 3, limit: 609042 };
+		assert_eq!(miner.mine("abcdef", 5), None);
+		let miner = Miner { threads: 3, limit: 609043 };
+		assert_eq!(miner.mine("abcdef", 5), Some(609043));
+	}
+
+	#[test]
+	fn progress_ends_with_every_nonce_hashed() {
+		let mut reports = Vec::new();
+		let miner = Miner { threads: 2, limit: 20000 };
+		let found = miner.mine_reporting("abcdef", 4, std::time::Duration::from_millis(1), |progress| reports.push(progress));
+		let last = reports.last().expect("there's always a last report");
+		assert!(last.hashed >= found.unwrap_or(20000));
+		assert!(reports.windows(2).all(|pair| pair[0].hashed <= pair[1].hashed));
+	}
+
+	#[test]
+	fn generated_keys_mine_the_lowest_nonce() {
+		for seed in 0..5 {
+			let text = aoc_common::generate::<Day4>(seed, None);
+			let key = Day4::parse(&PuzzleInput::from_text("generated", &text).unwrap()).unwrap();
+			assert_eq!(key, text);
+
+			let lowest = (1..).find(|nonce| md5(&format!("{}{}", key, nonce)).starts_with("000")).unwrap();
+			assert_eq!(Miner { threads: 2, limit: miner::MAX_NONCE }.mine(&key, 3), Some(lowest));
+		}
+	}
+}
@@ -1,57 +1,111 @@
-extern crate crypto;
+extern crate aoc_common;
+extern crate day_4;
 
-use crypto::md5::Md5;
-use crypto::digest::Digest;
+use std::env;
+use std::time::Duration;
 
-fn md5(input: &str) -> String {
-	let mut digest = Md5::new();
-	digest.input(&input.as_bytes());
-	
-	let result_str = digest.result_str();
-	return result_str;
-}
+use aoc_common::{LoadError, ParseError, PuzzleInput, Solution, Token, Tokens};
+use day_4::{miner, Day4, Miner};
 
-fn check_md5_value(buffer: [u8; 16]) -> bool {
-	buffer[0] == 0 
-	&& buffer[1] == 0 
-	&& buffer[2] == 0
+// Prints the answers, or mines one coin:
+//
+//     day_4 --zeros 7                   the lowest nonce with seven zeros
+//     day_4 --key abcdef --zeros 5      for another secret key
+//
+// with
+//
+//     --threads N        mine on N threads instead of one per CPU
+//     --limit N          give up after nonce N
+//     --progress         print how many hashes have been tried every second
+struct Options {
+	key: Option<String>,
+	zeros: Option<u32>,
+	miner: Miner,
+	progress: bool,
 }
 
-fn print_md5_buff(buffer: [u8; 16]) {
+// The argument after `flag`, which must be a single word.
+fn value<'a>(args: &mut impl Iterator<Item = Tokens<'a>>, flag: &Tokens<'a>, expected: &str) -> Result<(Tokens<'a>, Token<'a>), ParseError> {
+	let mut arg = args.next().ok_or_else(|| flag.error_at_end(expected))?;
+	let token = arg.next(expected)?;
+	arg.end()?;
+	Ok((arg, token))
+}
 
-	for i in 0..16 {
-		print!("{:02x} ", buffer[i]);
+// Reads each argument as if it were a line of input, for its diagnostics. The
+// key is taken whole, so it can have spaces in it.
+fn read_args(args: &[String]) -> Result<Options, LoadError> {
+	let mut args = args.iter().enumerate().map(|(idx, arg)| Tokens::new("argument", idx + 1, arg));
+	let mut options = Options { key: None, zeros: None, miner: Miner::default(), progress: false };
+	let expected = "--key, --zeros, --threads, --limit or --progress";
+	while let Some(mut flag) = args.next() {
+		let token = flag.next(expected)?;
+		flag.end()?;
+		match token.text {
+			"--key" => {
+				let key = args.next().ok_or_else(|| flag.error_at_end("the secret key"))?;
+				options.key = Some(key.text().to_string());
+			}
+			"--zeros" => {
+				let (arg, count) = value(&mut args, &flag, "how many zeros")?;
+				match arg.parse(count, "how many zeros")? {
+					zeros if zeros > miner::MAX_DIFFICULTY => {
+						return Err(arg.error(count, &format!("at most {} zeros", miner::MAX_DIFFICULTY)).into())
+					}
+					zeros => options.zeros = Some(zeros),
+				}
+			}
+			"--threads" => {
+				let (arg, threads) = value(&mut args, &flag, "how many threads")?;
+				match arg.parse(threads, "how many threads")? {
+					0 => return Err(arg.error(threads, "at least one thread").into()),
+					threads => options.miner.threads = threads,
+				}
+			}
+			"--limit" => {
+				let (arg, limit) = value(&mut args, &flag, "the highest nonce to try")?;
+				match arg.parse(limit, "the highest nonce to try")? {
+					nonce if nonce > miner::MAX_NONCE => {
+						return Err(arg.error(limit, &format!("a limit of at most {}", miner::MAX_NONCE)).into())
+					}
+					nonce => options.miner.limit = nonce,
+				}
+			}
+			"--progress" => options.progress = true,
+			_ => return Err(flag.error(token, expected).into()),
+		}
 	}
-	println!("");
+	Ok(options)
 }
- 
-fn main() {
-
-	let test_val = "abcdef609043";
-	let test_hash = "000001dbbfa3a5c83a2d506429c7b00e";
-	assert!(md5(test_val) == test_hash);
-	
-	// Md5 hashes are 128 bits (16 bytes)
-	let mut digest = Md5::new();
-	let mut buffer: [u8; 16] = [0; 16];
-
-	// Search for hash with "00000" prefix
-	let input_prefix = "iwrupvqb".to_string();
-	for num in 1..10000000 {
-		let num_str = num.to_string();
-		let value = String::new() + &input_prefix + &num_str;
-		
-		digest.input(&value.as_bytes());
-		digest.result(&mut buffer);
-		
-		if check_md5_value(buffer) {
-			print_md5_buff(buffer);
-			println!("Value = {}", num_str);
-			break;
+
+fn main() -> Result<(), LoadError> {
+	let options = read_args(&env::args().skip(1).collect::<Vec<String>>())?;
+	let input_prefix = match options.key {
+		Some(key) => key,
+		None => Day4::parse(&PuzzleInput::from_path(day_4::INPUT)?)?,
+	};
+
+	let zeros = match options.zeros {
+		Some(zeros) => zeros,
+		None => {
+			println!("day 4 part 1: {}", Day4::part1(&input_prefix));
+			println!("day 4 part 2: {}", Day4::part2(&input_prefix));
+			return Ok(());
 		}
-		
-		digest.reset();		
+	};
 
+	let found = if options.progress {
+		options.miner.mine_reporting(&input_prefix, zeros, Duration::from_secs(1), |progress| {
+			eprintln!("{} hashes in {:.1}s, {:.0} per second",
+				progress.hashed, progress.elapsed.as_secs_f64(), progress.rate());
+		})
+	} else {
+		options.miner.mine(&input_prefix, zeros)
+	};
+	match found {
+		Some(nonce) => println!("{}{} hashes to {}", input_prefix, nonce, day_4::md5(&format!("{}{}", input_prefix, nonce))),
+		None => println!("no nonce up to {} has {} zeros", options.miner.limit, zeros),
 	}
-	
-}
\ No newline at end of file
+
+	Ok(())
+}
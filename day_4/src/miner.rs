@@ -0,0 +1,184 @@
+//! Mines AdventCoins on several threads.
+//!
+//! A nonce is a positive number whose decimal digits, after the secret key,
+//! hash to an MD5 starting with `difficulty` zero hex digits. Threads take
+//! batches of nonces in increasing order, and only stop taking them once a
+//! batch starts past the lowest nonce found, so every smaller nonce is checked
+//! and the answer is the same however many threads there are.
+
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crypto::digest::Digest;
+use crypto::md5::Md5;
+
+/// The most zeros an MD5 hash can start with.
+pub const MAX_DIFFICULTY: u32 = 32;
+
+/// The highest nonce a search can try. `u64::MAX` is left over to stand for
+/// no nonce found.
+pub const MAX_NONCE: u64 = u64::MAX - 1;
+
+// Nonces a thread takes at a time
+const BATCH: u64 = 4096;
+
+/// How far a search has got.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Progress {
+	/// Nonces hashed so far, by every thread.
+	pub hashed: u64,
+	pub elapsed: Duration,
+}
+
+impl Progress {
+	/// Hashes per second.
+	pub fn rate(&self) -> f64 {
+		let seconds = self.elapsed.as_secs_f64();
+		if seconds == 0.0 {
+			return 0.0;
+		}
+		self.hashed as f64 / seconds
+	}
+}
+
+/// Whether `hash` starts with `difficulty` zero hex digits.
+pub fn has_zeros(hash: &[u8; 16], difficulty: u32) -> bool {
+	let bytes = (difficulty / 2) as usize;
+	hash[..bytes].iter().all(|&byte| byte == 0) && (difficulty.is_multiple_of(2) || hash[bytes] < 0x10)
+}
+
+/// How to search for a nonce.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Miner {
+	pub threads: usize,
+	/// The highest nonce to try. Limits over [`MAX_NONCE`] stop there.
+	pub limit: u64,
+}
+
+impl Default for Miner {
+	/// One thread per CPU, and no limit.
+	fn default() -> Miner {
+		Miner {
+			threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+			limit: MAX_NONCE,
+		}
+	}
+}
+
+// What the threads share
+struct Search {
+	keyed: Md5,
+	difficulty: u32,
+	limit: u64,
+	// Start of the next batch to take
+	next: AtomicU64,
+	// Lowest nonce found so far, or u64::MAX, which is never tried
+	found: AtomicU64,
+	hashed: AtomicU64,
+}
+
+impl Search {
+	fn work(&self) {
+		let mut digits = String::new();
+		let mut hash = [0; 16];
+		loop {
+			// Past the limit, `next` stays put rather than wrapping around to 0.
+			let first = match self.next.fetch_update(Ordering::Relaxed, Ordering::Relaxed,
+				|next| (next <= self.limit).then(|| next.saturating_add(BATCH))) {
+				Ok(first) if first <= self.found.load(Ordering::Relaxed) => first,
+				_ => return,
+			};
+			let last = first.saturating_add(BATCH - 1).min(self.limit);
+			let mut hashed = 0;
+			for nonce in first..=last {
+				let mut digest = self.keyed;
+				digits.clear();
+				write!(digits, "{}", nonce).expect("writing to a String can't fail");
+				digest.input(digits.as_bytes());
+				digest.result(&mut hash);
+				hashed += 1;
+				if has_zeros(&hash, self.difficulty) {
+					self.found.fetch_min(nonce, Ordering::Relaxed);
+					break;
+				}
+			}
+			self.hashed.fetch_add(hashed, Ordering::Relaxed);
+		}
+	}
+
+	fn progress(&self, start: Instant) -> Progress {
+		Progress { hashed: self.hashed.load(Ordering::Relaxed), elapsed: start.elapsed() }
+	}
+}
+
+impl Miner {
+	/// The lowest nonce up to the limit that mines a coin for `key`, if any.
+	///
+	/// Panics if `difficulty` is over [`MAX_DIFFICULTY`] or there are no
+	/// threads.
+	pub fn mine(&self, key: &str, difficulty: u32) -> Option<u64> {
+		self.search(key, difficulty, None)
+	}
+
+	/// Like [`Miner::mine`], but calls `report` every `interval` while it
+	/// searches, and once more at the end.
+	pub fn mine_reporting(&self, key: &str, difficulty: u32, interval: Duration, mut report: impl FnMut(Progress)) -> Option<u64> {
+		self.search(key, difficulty, Some((interval, &mut report)))
+	}
+
+	fn search(&self, key: &str, difficulty: u32, mut progress: Option<(Duration, &mut dyn FnMut(Progress))>) -> Option<u64> {
+		assert!(difficulty <= MAX_DIFFICULTY, "an MD5 hash has only {} hex digits, not {}", MAX_DIFFICULTY, difficulty);
+		assert!(self.threads > 0, "mining needs at least one thread");
+
+		let mut keyed = Md5::new();
+		keyed.input(key.as_bytes());
+		let search = Search {
+			keyed,
+			difficulty,
+			limit: self.limit.min(MAX_NONCE),
+			next: AtomicU64::new(1),
+			found: AtomicU64::new(u64::MAX),
+			hashed: AtomicU64::new(0),
+		};
+
+		let start = Instant::now();
+		let running = AtomicUsize::new(self.threads);
+		thread::scope(|scope| {
+			let main = thread::current();
+			for _ in 0..self.threads {
+				let (search, running, main) = (&search, &running, main.clone());
+				scope.spawn(move || {
+					search.work();
+					running.fetch_sub(1, Ordering::Release);
+					main.unpark();
+				});
+			}
+
+			// Workers wake this thread as they finish, so it can report until
+			// the last one does.
+			if let Some((interval, ref mut report)) = progress {
+				let mut due = start + interval;
+				while running.load(Ordering::Acquire) > 0 {
+					thread::park_timeout(due.saturating_duration_since(Instant::now()));
+					if Instant::now() >= due {
+						report(search.progress(start));
+						due += interval;
+					}
+				}
+			}
+		});
+		if let Some((_, report)) = progress {
+			report(search.progress(start));
+		}
+
+		match search.found.into_inner() {
+			u64::MAX => None,
+			nonce => {
+				debug!("{}{} hashes to {}", key, nonce, crate::md5(&format!("{}{}", key, nonce)));
+				Some(nonce)
+			}
+		}
+	}
+}
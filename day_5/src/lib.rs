@@ -0,0 +1,178 @@
+//! Day 5: Doesn't He Have Intern-Elves For This?
+//!
+//! Sorting a list of strings into naughty and nice.
+
+extern crate aoc_common;
+#[macro_use] extern crate log;
+
+use aoc_common::{Generator, ParseError, PuzzleInput, Rng, Solution};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day5.txt");
+
+// It contains at least three vowels (aeiou only), like aei, xazegov, or aeiouaeiouaeiou.
+// It contains at least one letter that appears twice in a row, like  xx, abcdde (dd), or aabbccdd (aa, bb, cc, or dd).
+// It does not contain the strings ab, cd, pq, or xy, even if they are part of one of the other requirements.
+
+fn is_nice_string(string: &str) -> bool {
+	let has_bad_str = !string.contains("ab") &&
+					  !string.contains("cd") &&
+					  !string.contains("pq") &&
+					  !string.contains("xy");
+					  
+	let mut vowels = 0;
+	let mut last_char = '.';
+	let mut has_double = false;
+	
+	for c in string.chars() {
+		if c == 'a' || c == 'e' || c == 'i' || c == 'o' || c == 'u' {
+			vowels += 1;
+		}
+		
+		if c == last_char {
+			has_double = true;
+		}
+		
+		last_char = c;
+	}
+					  
+	has_bad_str && vowels >= 3 && has_double
+}
+
+
+// It contains a pair of any two letters that appears at least twice in the string without overlapping, like xyxy (xy) or aabcdefgaa (aa), but not like aaa (aa, but it overlaps).
+// It contains at least one letter which repeats with exactly one letter between them, like xyx, abcdefeghi (efe), or even aaa.
+fn is_nicer_string(string: &str) -> bool {
+
+	let mut has_min_two_pairs = false;
+	let mut has_sep_dupe = false;
+	let mut one_back = '.';
+	let mut two_back = '.';
+	for c in string.chars() {
+		if c == two_back {
+			has_sep_dupe = true;
+		}
+		two_back = one_back;
+		one_back = c;
+		
+		// Check for pattern in the remaining string
+		let pattern = two_back.to_string() + &one_back.to_string();
+		let match_count = string.matches(&pattern).count();
+		
+		if match_count > 1 {
+			has_min_two_pairs = true;
+		}
+	}
+	
+	has_sep_dupe && has_min_two_pairs
+}
+
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day5;
+
+impl Solution for Day5 {
+	type Input = Vec<String>;
+	type Part1 = usize;
+	type Part2 = usize;
+
+	// One string per line
+	fn parse(input: &PuzzleInput) -> Result<Vec<String>, ParseError> {
+		input.tokens()
+			.map(|mut line| {
+				let string = line.next("a string")?;
+				line.end()?;
+				Ok(string.text.to_string())
+			})
+			.collect()
+	}
+
+	fn part1(lines: &Vec<String>) -> usize {
+		let nice_results: Vec<&String> = lines.iter()
+							.filter(|x| is_nice_string(x))
+							.collect();
+
+		nice_results.len()
+	}
+
+	fn part2(lines: &Vec<String>) -> usize {
+		let nicer_results: Vec<&String> = lines.iter()
+							.filter(|x| is_nicer_string(x))
+							.collect();
+
+		let mut nice_count = 0;
+		for string in nicer_results {
+			trace!("{}", string);
+			nice_count += 1;
+		}
+		nice_count
+	}
+}
+
+// `size` strings of 16 random lowercase letters, about a quarter of them nice.
+impl Generator for Day5 {
+	const DEFAULT_SIZE: usize = 1000;
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		let strings: Vec<String> = (0..size)
+			.map(|_| (0..16).map(|_| (b'a' + rng.below(26) as u8) as char).collect())
+			.collect();
+		strings.join("\n")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn nice_examples() {
+		assert!(is_nice_string("ugknbfddgicrmopn"));
+		assert!(is_nice_string("aaa"));
+		assert!(!is_nice_string("jchzalrnumimnmhp"));
+		assert!(!is_nice_string("haegwjzuvuyypxyu"));
+		assert!(!is_nice_string("dvszwmarrgswjxmb"));
+	}
+
+	#[test]
+	fn nicer_examples() {
+		assert!(is_nicer_string("qjhvhtzxzqqjkmpb"));
+		assert!(is_nicer_string("xxyxx"));
+		assert!(!is_nicer_string("uurcxstgmygtbstg"));
+		assert!(!is_nicer_string("ieodomkazucvgmuy"));
+	}
+
+	#[test]
+	fn counts_nice_lines() {
+		let text = "ugknbfddgicrmopn\njchzalrnumimnmhp\nqjhvhtzxzqqjkmpb\nxxyxx";
+		let lines = Day5::parse(&PuzzleInput::from_text("example", text).unwrap()).unwrap();
+		assert_eq!(Day5::part1(&lines), 1);
+		assert_eq!(Day5::part2(&lines), 2);
+	}
+
+	// The rules again, spelled out one at a time.
+	fn nice_by_rules(line: &str) -> bool {
+		let letters = line.as_bytes();
+		letters.iter().filter(|&&c| b"aeiou".contains(&c)).count() >= 3
+			&& letters.windows(2).any(|pair| pair[0] == pair[1])
+			&& !["ab", "cd", "pq", "xy"].iter().any(|bad| line.contains(bad))
+	}
+
+	fn nicer_by_rules(line: &str) -> bool {
+		let letters = line.as_bytes();
+		let repeated_pair = (0..letters.len().saturating_sub(1))
+			.any(|i| (i + 2..letters.len().saturating_sub(1)).any(|j| letters[i..i + 2] == letters[j..j + 2]));
+		repeated_pair && letters.windows(3).any(|triple| triple[0] == triple[2])
+	}
+
+	#[test]
+	fn generated_inputs_follow_the_rules() {
+		for seed in 0..20 {
+			let text = aoc_common::generate::<Day5>(seed, Some(100));
+			let lines = Day5::parse(&PuzzleInput::from_text("generated", &text).unwrap()).unwrap();
+			assert_eq!(lines.len(), 100);
+			assert_eq!(Day5::part1(&lines), text.lines().filter(|line| nice_by_rules(line)).count());
+			assert_eq!(Day5::part2(&lines), text.lines().filter(|line| nicer_by_rules(line)).count());
+		}
+	}
+}
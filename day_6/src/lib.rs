@@ -0,0 +1,241 @@
+//! Day 6: Probably a Fire Hazard
+//!
+//! A 1000x1000 grid of lights; [`switch_lights`] and [`dim_lights`] replay the
+//! instructions frame by frame.
+
+extern crate aoc_common;
+
+use std::cmp;
+
+use aoc_common::{Generator, Grid, ParseError, PuzzleInput, Rng, Solution, Token, Tokens};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day6.txt");
+
+#[derive(Debug)]
+struct Range<T> {
+	start: T,
+	end: T,
+}
+
+#[derive(Debug)]
+enum NewState {
+	Toggle,
+	On,
+	Off,
+}
+
+/// One instruction, like "toggle 461,550 through 564,900".
+#[derive(Debug)]
+pub struct Command {
+	new_state: NewState,
+	x_range: Range<usize>,
+	y_range: Range<usize>,
+}
+
+// Parses a corner like "12,823". Both coordinates must be on the 1000x1000 grid.
+fn parse_corner<'a>(line: &Tokens<'a>, token: Token<'a>) -> Result<(usize, usize), ParseError> {
+	let coords = token.split(',');
+	if coords.len() != 2 {
+		return Err(line.error(token, "a corner like 12,823"));
+	}
+
+	let mut corner = [0; 2];
+	for (value, &coord) in corner.iter_mut().zip(coords.iter()) {
+		*value = line.parse::<usize>(coord, "a coordinate from 0 to 999")?;
+		if *value >= 1000 {
+			return Err(line.error(coord, "a coordinate from 0 to 999"));
+		}
+	}
+	Ok((corner[0], corner[1]))
+}
+
+// Formats:
+// 			turn off 12,823 through 102,934
+// 			toggle 756,965 through 812,992
+fn parse_command(line: &mut Tokens) -> Result<Command, ParseError> {
+
+	let verb = line.next("'turn' or 'toggle'")?;
+	let command = match verb.text {
+		"toggle" => NewState::Toggle,
+		"turn" => {
+			let state = line.next("'on' or 'off'")?;
+			match state.text {
+				"on" => NewState::On,
+				"off" => NewState::Off,
+				_ => return Err(line.error(state, "'on' or 'off'")),
+			}
+		}
+		_ => return Err(line.error(verb, "'turn' or 'toggle'")),
+	};
+
+	let start = line.next("a corner like 12,823")?;
+	let (x1, y1) = parse_corner(line, start)?;
+	line.literal("through")?;
+	let end = line.next("a corner like 102,934")?;
+	let (x2, y2) = parse_corner(line, end)?;
+	if x2 < x1 || y2 < y1 {
+		return Err(line.error(end, &format!("a corner no smaller than {}", start.text)));
+	}
+	line.end()?;
+
+	let x_range = Range {start: x1, end: x2+1};
+	let y_range = Range {start: y1, end: y2+1};
+	
+	Ok(Command {new_state:command, x_range, y_range})
+}
+
+fn toggle_lights(grid: &mut Grid<bool>, cmd: &Command) {
+
+	for y_idx in cmd.y_range.start..cmd.y_range.end {
+		for light in &mut grid.row_mut(y_idx)[cmd.x_range.start..cmd.x_range.end] {
+			*light =
+				match cmd.new_state {
+					NewState::On => true,
+					NewState::Off => false,
+					NewState::Toggle => !*light,
+				}
+		}
+	}
+}
+
+fn change_light_brightness(grid: &mut Grid<i32>, cmd: &Command) {
+	
+	for y_idx in cmd.y_range.start..cmd.y_range.end {
+		for light in &mut grid.row_mut(y_idx)[cmd.x_range.start..cmd.x_range.end] {
+			*light = 
+				match cmd.new_state {
+					NewState::On => *light + 1,
+					NewState::Off => cmp::max(0, *light - 1),
+					NewState::Toggle => *light + 2,
+				};
+		}
+	}
+}
+
+/// Follows the instructions as on/off switches, calling `frame` with the grid
+/// after each one.
+pub fn switch_lights(command_list: &[Command], mut frame: impl FnMut(&Grid<bool>)) -> Grid<bool> {
+	let mut grid = Grid::new(1000, 1000, false);
+	for cmd in command_list {
+		toggle_lights(&mut grid, cmd);
+		frame(&grid);
+	}
+	grid
+}
+
+/// Follows the instructions as brightness changes, calling `frame` with the
+/// grid after each one.
+pub fn dim_lights(command_list: &[Command], mut frame: impl FnMut(&Grid<i32>)) -> Grid<i32> {
+	let mut grid = Grid::new(1000, 1000, 0);
+	for cmd in command_list {
+		change_light_brightness(&mut grid, cmd);
+		frame(&grid);
+	}
+	grid
+}
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day6;
+
+impl Solution for Day6 {
+	type Input = Vec<Command>;
+	type Part1 = usize;
+	type Part2 = i32;
+
+	fn parse(input: &PuzzleInput) -> Result<Vec<Command>, ParseError> {
+		input.tokens().map(|mut line| parse_command(&mut line)).collect()
+	}
+
+	fn part1(command_list: &Vec<Command>) -> usize {
+		// Count lights
+		switch_lights(command_list, |_| {}).iter().filter(|&&on| on).count()
+	}
+
+	// Part 2: change brightness
+	fn part2(command_list: &Vec<Command>) -> i32 {
+		dim_lights(command_list, |_| {}).iter().sum()
+	}
+}
+
+// A random span of the grid along one axis.
+fn random_span(rng: &mut Rng) -> (usize, usize) {
+	let (a, b) = (rng.below(1000), rng.below(1000));
+	(a.min(b), a.max(b))
+}
+
+// `size` instructions over random rectangles of the grid.
+impl Generator for Day6 {
+	const DEFAULT_SIZE: usize = 300;
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		let instructions: Vec<String> = (0..size)
+			.map(|_| {
+				let verb = ["turn on", "turn off", "toggle"][rng.below(3)];
+				let (x1, x2) = random_span(rng);
+				let (y1, y2) = random_span(rng);
+				format!("{} {},{} through {},{}", verb, x1, y1, x2, y2)
+			})
+			.collect();
+		instructions.join("\n")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn example(text: &str) -> Vec<Command> {
+		Day6::parse(&PuzzleInput::from_text("example", text).unwrap()).unwrap()
+	}
+
+	#[test]
+	fn part1_examples() {
+		assert_eq!(Day6::part1(&example("turn on 0,0 through 999,999")), 1_000_000);
+		assert_eq!(Day6::part1(&example("toggle 0,0 through 999,0")), 1000);
+		assert_eq!(Day6::part1(&example("turn on 0,0 through 999,999\n\
+		                                 turn off 499,499 through 500,500")), 999_996);
+	}
+
+	#[test]
+	fn part2_examples() {
+		assert_eq!(Day6::part2(&example("turn on 0,0 through 0,0")), 1);
+		assert_eq!(Day6::part2(&example("toggle 0,0 through 999,999")), 2_000_000);
+	}
+
+	#[test]
+	fn one_frame_per_instruction() {
+		let mut lit = Vec::new();
+		switch_lights(&example("turn on 0,0 through 9,9\ntoggle 0,0 through 4,9"),
+		              |grid| lit.push(grid.iter().filter(|&&on| on).count()));
+		assert_eq!(lit, [100, 50]);
+	}
+
+	#[test]
+	fn generated_inputs_match_each_light() {
+		for seed in 0..3 {
+			let text = aoc_common::generate::<Day6>(seed, Some(20));
+			let commands = example(&text);
+			assert_eq!(commands.len(), 20);
+
+			// Follow every light through the commands that cover it.
+			let (mut lit, mut brightness) = (0, 0);
+			for y in 0..1000 {
+				for x in 0..1000 {
+					let covering = commands.iter().filter(|cmd| {
+						(cmd.x_range.start..cmd.x_range.end).contains(&x) && (cmd.y_range.start..cmd.y_range.end).contains(&y)
+					});
+					let (on, level) = covering.fold((false, 0), |(on, level), cmd| match cmd.new_state {
+						NewState::On => (true, level + 1),
+						NewState::Off => (false, cmp::max(0, level - 1)),
+						NewState::Toggle => (!on, level + 2),
+					});
+					lit += on as usize;
+					brightness += level;
+				}
+			}
+			assert_eq!(Day6::part1(&commands), lit);
+			assert_eq!(Day6::part2(&commands), brightness);
+		}
+	}
+}
@@ -0,0 +1,371 @@
+//! Day 7: Some Assembly Required
+//!
+//! Evaluates a circuit of bitwise logic gates.
+
+extern crate aoc_common;
+#[macro_use] extern crate log;
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use aoc_common::{Generator, ParseError, PuzzleInput, Rng, Solution, Token, Tokens};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day7.txt");
+
+/// A gate's input: a constant or the signal on another wire.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Signal {
+    Value(u16),
+    Wire(String),
+}
+
+/// What drives a wire. Shifts are by a constant from 0 to 15.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Gate {
+    Direct(Signal),
+    Not(Signal),
+    And(Signal, Signal),
+    Or(Signal, Signal),
+    LShift(Signal, u32),
+    RShift(Signal, u32),
+}
+
+/// A gate and the wire it drives, like `x AND y -> d`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Connection {
+    pub gate: Gate,
+    pub output: String,
+}
+
+// The signal's value, if its wire has one yet.
+fn get_input_value(wires: &HashMap<&str, u16>, signal: &Signal) -> Option<u16> {
+    match *signal {
+        Signal::Value(value) => Some(value),
+        Signal::Wire(ref wire) => wires.get(wire.as_str()).cloned(),
+    }
+}
+
+impl Gate {
+    // The gate's output, once all its inputs have signals.
+    fn output(&self, wires: &HashMap<&str, u16>) -> Option<u16> {
+        let value = |signal| get_input_value(wires, signal);
+        Some(match *self {
+            Gate::Direct(ref input) => value(input)?,
+            Gate::Not(ref input) => !value(input)?,
+            Gate::And(ref left, ref right) => value(left)? & value(right)?,
+            Gate::Or(ref left, ref right) => value(left)? | value(right)?,
+            Gate::LShift(ref input, shift) => value(input)? << shift,
+            Gate::RShift(ref input, shift) => value(input)? >> shift,
+        })
+    }
+}
+
+// Sets every wire whose gate's inputs get a signal. Wires that already have a
+// signal are overridden, so the gates driving them are skipped. Returns the
+// gates that never got all their inputs, because they're in or after a loop,
+// rather than retrying them forever.
+fn process_gate_queue<'a>(gates: &'a [Connection], wires: &mut HashMap<&'a str, u16>) -> Vec<usize> {
+    let mut remaining_gates: VecDeque<usize> = (0..gates.len())
+        .filter(|&idx| !wires.contains_key(gates[idx].output.as_str()))
+        .collect();
+
+    // Gates retried since the last one that worked. A whole pass of those
+    // means nothing left can get a signal.
+    let mut retried = 0;
+    while let Some(idx) = remaining_gates.pop_front() {
+        let connection = &gates[idx];
+        match connection.gate.output(wires) {
+            Some(value_out) => {
+                trace!("{:?} \t=> {} ({})", connection.gate, connection.output, value_out);
+                wires.insert(&connection.output, value_out);
+                retried = 0;
+            }
+            None => {
+                remaining_gates.push_back(idx); // Return the unprocessed gate to the queue
+                retried += 1;
+                if retried == remaining_gates.len() {
+                    warn!("{} gates never get a signal", retried);
+                    break;
+                }
+            }
+        }
+    }
+    remaining_gates.into()
+}
+
+// A signal is a 16-bit constant or a lowercase wire ID.
+fn parse_signal<'a>(line: &Tokens<'a>, token: Token<'a>, expected: &str) -> Result<Signal, ParseError> {
+    if !token.text.is_empty() && token.text.chars().all(|c| c.is_ascii_lowercase()) {
+        Ok(Signal::Wire(token.text.to_string()))
+    } else {
+        line.parse(token, expected).map(Signal::Value)
+    }
+}
+
+// Shifting a 16-bit signal by 16 or more would lose every bit.
+fn parse_shift<'a>(line: &mut Tokens<'a>) -> Result<u32, ParseError> {
+    let expected = "a shift from 0 to 15";
+    let token = line.next(expected)?;
+    match line.parse(token, expected)? {
+        shift if shift < 16 => Ok(shift),
+        _ => Err(line.error(token, expected)),
+    }
+}
+
+// Parses one of these shapes:
+//     123 -> x
+//     NOT x -> h
+//     x AND y -> d
+//     x LSHIFT 2 -> f
+// Returns the output wire's token and the tokens of every input wire as well.
+fn parse_gate<'a>(line: &mut Tokens<'a>) -> Result<(Connection, Token<'a>, Vec<Token<'a>>), ParseError> {
+    let mut wires_in = Vec::new();
+    let mut signal = |line: &Tokens<'a>, token: Token<'a>, expected: &str| {
+        let signal = parse_signal(line, token, expected)?;
+        if let Signal::Wire(_) = signal {
+            wires_in.push(token);
+        }
+        Ok(signal)
+    };
+
+    let first = line.next("a signal or 'NOT'")?;
+    let gate = if first.text == "NOT" {
+        let input = line.next("a wire or signal")?;
+        let gate = Gate::Not(signal(line, input, "a wire or signal")?);
+        line.literal("->")?;
+        gate
+    } else {
+        let left = signal(line, first, "a signal or 'NOT'")?;
+        let operator = line.next("a gate or '->'")?;
+        let gate = match operator.text {
+            "->" => Gate::Direct(left),
+            "LSHIFT" => Gate::LShift(left, parse_shift(line)?),
+            "RSHIFT" => Gate::RShift(left, parse_shift(line)?),
+            "AND" | "OR" => {
+                let input = line.next("a wire or signal")?;
+                let right = signal(line, input, "a wire or signal")?;
+                if operator.text == "AND" { Gate::And(left, right) } else { Gate::Or(left, right) }
+            }
+            _ => return Err(line.error(operator, "'AND', 'OR', 'LSHIFT', 'RSHIFT' or '->'")),
+        };
+        if operator.text != "->" {
+            line.literal("->")?;
+        }
+        gate
+    };
+
+    let output = line.next("an output wire")?;
+    if !output.text.chars().all(|c| c.is_ascii_lowercase()) {
+        return Err(line.error(output, "an output wire"));
+    }
+    line.end()?;
+    Ok((Connection { gate, output: output.text.to_string() }, output, wires_in))
+}
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<Connection>;
+    type Part1 = u16;
+    type Part2 = u16;
+
+    // Besides each line's shape, checks that the circuit can be solved: every
+    // wire read from is driven by a gate, wire 'a' is, and nothing depends on
+    // its own signal.
+    fn parse(input: &PuzzleInput) -> Result<Vec<Connection>, ParseError> {
+        let mut lines = Vec::new();
+        let mut gates = Vec::new();
+        for mut line in input.tokens() {
+            let (connection, output, wires_in) = parse_gate(&mut line)?;
+            gates.push(connection);
+            lines.push((line, output, wires_in));
+        }
+
+        let driven: HashSet<&str> = gates.iter().map(|connection| connection.output.as_str()).collect();
+        for (line, _, wires_in) in &lines {
+            if let Some(&wire) = wires_in.iter().find(|wire| !driven.contains(wire.text)) {
+                return Err(line.error(wire, "a wire driven by a gate"));
+            }
+        }
+        if !driven.contains("a") {
+            return Err(input.last_line().error_at_end("a gate driving wire 'a'"));
+        }
+
+        let mut wires = HashMap::new();
+        if let Some(&idx) = process_gate_queue(&gates, &mut wires).first() {
+            let (ref line, output, _) = lines[idx];
+            return Err(line.error(output, "a wire that doesn't depend on its own signal"));
+        }
+        Ok(gates)
+    }
+
+    fn part1(gates: &Vec<Connection>) -> u16 {
+        let mut wires: HashMap<&str, u16> = HashMap::new();
+
+        process_gate_queue(gates, &mut wires);
+
+        wires["a"]
+    }
+
+    fn part2(gates: &Vec<Connection>) -> u16 {
+        let mut wires: HashMap<&str, u16> = HashMap::new();
+
+        process_gate_queue(gates, &mut wires);
+
+        // Clear wires, override 'b' with the old signal on 'a', and reprocess.
+        let old_a_value = wires["a"];
+        wires.clear();
+        wires.insert("b", old_a_value);
+
+        debug!("Part 2 in: {:?}", wires);
+
+        process_gate_queue(gates, &mut wires);
+
+        wires["a"]
+    }
+}
+
+// Name of the `index`th generated wire: "c" to "z", then "aa", "ab", ...
+// "a" and "b" are left for the puzzle's own wires.
+fn wire_name(index: usize) -> String {
+    let mut index = index + 2;
+    let mut name = Vec::new();
+    loop {
+        name.push(b'a' + (index % 26) as u8);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    name.reverse();
+    String::from_utf8(name).expect("wire names are ASCII")
+}
+
+// Mostly one of the last few wires, so the circuit ends up deep rather than wide.
+fn pick_wire<'a>(rng: &mut Rng, wires: &'a [String]) -> &'a str {
+    let count = if rng.chance(0.7) { wires.len().min(8) } else { wires.len() };
+    &wires[wires.len() - 1 - rng.below(count)]
+}
+
+// The left side of a gate whose inputs are all in `wires`.
+fn random_gate(rng: &mut Rng, wires: &[String]) -> String {
+    match rng.below(8) {
+        0 => rng.between(0, 65535).to_string(),
+        1 => pick_wire(rng, wires).to_string(),
+        2 => format!("NOT {}", pick_wire(rng, wires)),
+        3 => format!("1 AND {}", pick_wire(rng, wires)),
+        4 => format!("{} AND {}", pick_wire(rng, wires), pick_wire(rng, wires)),
+        5 => format!("{} OR {}", pick_wire(rng, wires), pick_wire(rng, wires)),
+        6 => format!("{} LSHIFT {}", pick_wire(rng, wires), rng.between(1, 15)),
+        _ => format!("{} RSHIFT {}", pick_wire(rng, wires), rng.between(1, 15)),
+    }
+}
+
+// A shuffled circuit of `size` wires with no loops. Like the real puzzle,
+// wire 'b' is set by a constant and feeds wire 'a'. The rest of the circuit
+// never reads 'b', and 'a' is NOT of b's bits under a nonzero mask and another
+// wire's bits elsewhere, so overriding 'b' with 'a' always changes 'a'.
+impl Generator for Day7 {
+    const DEFAULT_SIZE: usize = 339;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut wires: Vec<String> = Vec::new();
+        let mut gates = vec![format!("{} -> b", rng.between(0, 65535))];
+        for index in 0..size.max(8) - 7 {
+            let name = wire_name(index);
+            let gate = if wires.is_empty() { rng.between(0, 65535).to_string() } else { random_gate(rng, &wires) };
+            gates.push(format!("{} -> {}", gate, name));
+            wires.push(name);
+        }
+
+        let [mask, b_bits, not_mask, other_bits, mixed] = [0, 1, 2, 3, 4].map(|offset| wire_name(wires.len() + offset));
+        gates.push(format!("{} OR {} -> {}", pick_wire(rng, &wires), rng.between(1, 65535), mask));
+        gates.push(format!("b AND {} -> {}", mask, b_bits));
+        gates.push(format!("NOT {} -> {}", mask, not_mask));
+        gates.push(format!("{} AND {} -> {}", pick_wire(rng, &wires), not_mask, other_bits));
+        gates.push(format!("{} OR {} -> {}", b_bits, other_bits, mixed));
+        gates.push(format!("NOT {} -> a", mixed));
+
+        rng.shuffle(&mut gates);
+        gates.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day7_test.txt");
+
+    // The example circuit has no wire 'a', so connect each wire to it in turn.
+    fn signal_on(wire: &str) -> u16 {
+        let circuit = PuzzleInput::from_path(EXAMPLE).unwrap();
+        let text = format!("{}\n{} -> a", circuit.text(), wire);
+        let gates = Day7::parse(&PuzzleInput::from_text("example", &text).unwrap()).unwrap();
+        Day7::part1(&gates)
+    }
+
+    #[test]
+    fn example_circuit() {
+        let expected = [("d", 72), ("e", 507), ("f", 492), ("g", 114),
+                        ("h", 65412), ("i", 65079), ("x", 123), ("y", 456)];
+        for &(wire, signal) in &expected {
+            assert_eq!(signal_on(wire), signal, "wire {}", wire);
+        }
+    }
+
+    #[test]
+    fn part2_overrides_b() {
+        let text = "123 -> b\nb RSHIFT 1 -> a";
+        let gates = Day7::parse(&PuzzleInput::from_text("example", text).unwrap()).unwrap();
+        assert_eq!(Day7::part1(&gates), 61);
+        assert_eq!(Day7::part2(&gates), 30);
+    }
+
+    #[test]
+    fn unsolvable_circuits_are_rejected() {
+        let column = |text: &str| {
+            let error = Day7::parse(&PuzzleInput::from_text("example", text).unwrap()).unwrap_err();
+            (error.line, error.column)
+        };
+        // Nothing drives x
+        assert_eq!(column("1 -> y\nx AND y -> a"), (2, 1));
+        // No wire 'a'
+        assert_eq!(column("123 -> x\nx -> y"), (2, 7));
+        // Shifting every bit out
+        assert_eq!(column("1 -> x\nx LSHIFT 20 -> a"), (2, 10));
+        // A loop
+        assert_eq!(column("b -> c\nc -> b\nc -> a"), (1, 6));
+
+        let text = "1 -> x\nx LSHIFT 15 -> a";
+        let gates = Day7::parse(&PuzzleInput::from_text("example", text).unwrap()).unwrap();
+        assert_eq!(gates[1], Connection { gate: Gate::LShift(Signal::Wire("x".to_string()), 15), output: "a".to_string() });
+        assert_eq!(Day7::part1(&gates), 0x8000);
+    }
+
+    #[test]
+    fn generated_wire_names() {
+        assert_eq!(wire_name(0), "c");
+        assert_eq!(wire_name(23), "z");
+        assert_eq!(wire_name(24), "aa");
+    }
+
+    #[test]
+    fn generated_inputs_feed_a_back_into_b() {
+        for seed in 0..20 {
+            let text = aoc_common::generate::<Day7>(seed, Some(100));
+            let gates = Day7::parse(&PuzzleInput::from_text("generated", &text).unwrap()).unwrap();
+            assert_eq!(gates.len(), 100);
+            let (first, second) = (Day7::part1(&gates), Day7::part2(&gates));
+            assert_ne!(first, second, "seed {}", seed);
+
+            // Part 2 is part 1 of the same circuit with 'b' set to the first 'a'.
+            let overridden: Vec<String> = text.lines()
+                .map(|line| if line.ends_with(" -> b") { format!("{} -> b", first) } else { line.to_string() })
+                .collect();
+            let gates = Day7::parse(&PuzzleInput::from_text("generated", &overridden.join("\n")).unwrap()).unwrap();
+            assert_eq!(Day7::part1(&gates), second, "seed {}", seed);
+        }
+    }
+}
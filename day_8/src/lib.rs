@@ -0,0 +1,200 @@
+//! Day 8: Matchsticks
+//!
+//! Escaping and unescaping string literals.
+
+extern crate aoc_common;
+#[macro_use] extern crate log;
+
+use std::char;
+
+extern crate regex;
+use regex::Captures;
+
+extern crate rustc_serialize;
+use rustc_serialize::hex::FromHex;
+
+use aoc_common::{Generator, ParseError, PuzzleInput, Rng, Solution, Tokens};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day8.txt");
+
+fn strip_escape_chars(text: &str) -> String {
+	use regex::Regex;
+	
+	//println!("{}", text);
+	
+	// Identifies a backslash-hex value and converts the hex to a single char
+	// i.e. \x27 => 39 => '\''
+	let re_hex = Regex::new(r"\\[x]([0-9a-f]{2})").unwrap();
+	let result = re_hex.replace_all(text, |caps: &Captures| {
+		let chr_code: u32 = caps.at(1).unwrap_or("").from_hex().unwrap()[0] as u32;
+		let chr = char::from_u32(chr_code).unwrap();
+		//println!("\t{:?} -> {:?}", caps.at(1).unwrap_or(""), chr);
+		chr.to_string()
+	});
+	
+	// Replace escaped quotes and backslashes
+	let result2 = result.replace(r#"\""#, "\"").replace(r"\\", r"\");
+	
+	// Strip the first and last quotes
+	let re_quotes = Regex::new(r"(^.|.$)").unwrap();
+	let result3 = re_quotes.replace_all(&result2, "");
+	
+	//println!("{0: <45}  =>  {1: <45}", text, result3);
+	result3
+}
+
+fn add_escape_chars(text: &str) -> String {
+	let encoded = text.replace("\\", r"\\").replace("\"", r#"\""#);
+	format!("\"{}\"", encoded)
+}
+
+// Checks a line is a quoted string literal whose only escapes are \\, \" and
+// \x followed by two lowercase hex digits.
+fn check_literal(line: &Tokens) -> Result<(), ParseError> {
+	let text = line.text();
+	let bytes = text.as_bytes();
+	let found = |offset: usize, width: usize| {
+		format!("'{}'", text[offset..].chars().take(width).collect::<String>())
+	};
+
+	if bytes.len() < 2 || bytes[0] != b'"' || bytes[bytes.len() - 1] != b'"' {
+		let width = text.chars().count();
+		return Err(line.error_at(0, width, "a quoted string", &found(0, width)));
+	}
+
+	let end = bytes.len() - 1;
+	let mut idx = 1;
+	while idx < end {
+		match bytes[idx] {
+			b'\\' => {
+				let escape_len = match bytes.get(idx + 1) {
+					Some(b'\\') | Some(b'"') => 2,
+					Some(b'x') if idx + 4 <= end
+						&& bytes[idx + 2..idx + 4].iter().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(b)) => 4,
+					_ => {
+						let width = if idx + 1 < end { 2 } else { 1 };
+						return Err(line.error_at(idx, width, r#"an escape like \\, \" or \x27"#, &found(idx, width)));
+					}
+				};
+				idx += escape_len;
+			}
+			b'"' => return Err(line.error_at(idx, 1, r#"an escaped quote (\")"#, "'\"'")),
+			_ => idx += 1,
+		}
+	}
+	Ok(())
+}
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day8;
+
+impl Solution for Day8 {
+	type Input = Vec<String>;
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &PuzzleInput) -> Result<Vec<String>, ParseError> {
+		input.tokens()
+			.map(|line| {
+				check_literal(&line)?;
+				Ok(line.text().to_string())
+			})
+			.collect()
+	}
+
+	fn part1(strings: &Vec<String>) -> usize {
+		let mut total_raw = 0;
+		let mut total_str = 0;
+
+		for string in strings {
+
+			let stripped_string = strip_escape_chars(string);
+
+			let raw_length = string.chars().count();
+			let stripped_length = stripped_string.chars().count();
+
+			total_raw += raw_length;
+			total_str += stripped_length;
+
+			//println!("{:?} => {:?} \t::\t {:?} -> {:?}", string, stripped_string,
+			// raw_length, stripped_length);
+		}
+
+		total_raw - total_str
+	}
+
+	fn part2(strings: &Vec<String>) -> usize {
+		let mut total_raw = 0;
+		let mut total_encoded = 0;
+		for string in strings {
+			let encoded_string = add_escape_chars(string);
+			let raw_length = string.chars().count();
+			let encoded_length = encoded_string.chars().count();
+			total_raw += raw_length;
+			total_encoded += encoded_length;
+
+			trace!("{0: <45} => {1: <55}  ::  {2: >2} -> {3: >2}", string, encoded_string,
+			raw_length, encoded_length);
+		}
+
+		total_encoded - total_raw
+	}
+}
+
+// `size` string literals of up to 30 characters, about a third of them escapes.
+impl Generator for Day8 {
+	const DEFAULT_SIZE: usize = 300;
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		let literals: Vec<String> = (0..size)
+			.map(|_| {
+				let mut literal = String::from("\"");
+				for _ in 0..rng.below(31) {
+					match rng.below(10) {
+						0 => literal.push_str("\\\\"),
+						1 => literal.push_str("\\\""),
+						2 => literal.push_str(&format!("\\x{:02x}", rng.below(256))),
+						_ => literal.push((b'a' + rng.below(26) as u8) as char),
+					}
+				}
+				literal.push('"');
+				literal
+			})
+			.collect();
+		literals.join("\n")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn example() -> Vec<String> {
+		let input = PuzzleInput::from_path(concat!(env!("CARGO_MANIFEST_DIR"), "/day8_test.txt")).unwrap();
+		Day8::parse(&input).unwrap()
+	}
+
+	#[test]
+	fn part1_example() {
+		assert_eq!(Day8::part1(&example()), 23 - 11);
+	}
+
+	#[test]
+	fn part2_example() {
+		assert_eq!(Day8::part2(&example()), 42 - 23);
+	}
+
+	#[test]
+	fn generated_inputs_solve() {
+		for seed in 0..20 {
+			let text = aoc_common::generate::<Day8>(seed, Some(50));
+			let strings = Day8::parse(&PuzzleInput::from_text("generated", &text).unwrap()).unwrap();
+			Day8::part1(&strings);
+
+			// Encoding adds the outer quotes and one backslash per quote or backslash.
+			let added: usize = strings.iter().map(|s| 2 + s.matches(&['"', '\\'][..]).count()).sum();
+			assert_eq!(Day8::part2(&strings), added);
+		}
+	}
+}
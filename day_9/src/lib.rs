@@ -0,0 +1,228 @@
+//! Day 9: All in a Single Night
+//!
+//! Shortest and longest routes visiting every city once.
+
+extern crate aoc_common;
+
+use std::cmp;
+use std::collections::HashMap;
+
+extern crate nalgebra as na;
+use na::DMatrix;
+
+use aoc_common::{Generator, ParseError, PuzzleInput, Rng, Solution};
+
+/// Path to the bundled puzzle input.
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day9.txt");
+
+// ----------------------------------------------------------------------------
+
+fn get_city_index<'a>(city_indices: &mut HashMap<&'a str, usize>, name: &'a str) -> usize {
+	let next_open_index = city_indices.len();
+	*city_indices.entry(name).or_insert(next_open_index)
+}
+
+// ----------------------------------------------------------------------------
+
+// Lines look like "London to Dublin = 464".
+fn create_distance_mapping(input: &PuzzleInput) -> Result<(HashMap<&str, usize>, DMatrix<u32>), ParseError> {
+	
+	let mut city_indices: HashMap<&str, usize> = HashMap::new();
+	let mut routes: Vec<(usize, usize, u32)> = Vec::new();
+	
+	for mut line in input.tokens() {
+		let start_name = line.next("a city")?.text;
+		let start_idx = get_city_index(&mut city_indices, start_name);		
+		line.literal("to")?;
+		let end_name = line.next("a city")?.text;
+		let end_idx = get_city_index(&mut city_indices, end_name);		
+		line.literal("=")?;
+		let distance = line.number::<u32>("a distance")?;
+		line.end()?;
+		
+		routes.push((start_idx, end_idx, distance));
+	};
+	
+	// One row and column per city
+	let num_cities = city_indices.len();
+	let mut distances: DMatrix<u32> = DMatrix::zeros(num_cities, num_cities);
+	for (start_idx, end_idx, distance) in routes {
+		distances[(start_idx, end_idx)] = distance;
+		distances[(end_idx, start_idx)] = distance;
+	}
+	
+	Ok((city_indices, distances))
+}
+
+// ----------------------------------------------------------------------------
+
+fn find_min_path(distances: &DMatrix<u32>, city_indices: &[usize], previous_city: usize) -> u32 {
+
+	// Starting case: for each starting city
+	let is_starting_city = city_indices.len() == distances.nrows();
+
+	// Base case: one index left to visit
+	if city_indices.len() == 1 {
+		let next_city = city_indices[0];
+		return distances[(previous_city, next_city)];
+	}
+	
+	// Recursive case: shortest path through the remaining cities
+	let mut best_distance = u32::MAX;
+	for i in 0..city_indices.len() {
+		let mut remaining_indices = city_indices.to_vec();
+		let next_city = remaining_indices.swap_remove(i);
+		
+		// Handle special case of being in the starting city (distance zero to previous).
+		let this_dist = if is_starting_city {
+			0u32
+		} else {
+			distances[(previous_city, next_city)]
+		};
+		
+		let total_dist = this_dist + find_min_path(distances, &remaining_indices, next_city);
+		best_distance = cmp::min(total_dist, best_distance);
+	}
+	
+	best_distance
+}
+
+// ----------------------------------------------------------------------------
+
+fn find_max_path(distances: &DMatrix<u32>, city_indices: &[usize], previous_city: usize) -> u32 {
+
+	// Starting case: for each starting city
+	let is_starting_city = city_indices.len() == distances.nrows();
+
+	// Base case: one index left to visit
+	if city_indices.len() == 1 {
+		let next_city = city_indices[0];
+		return distances[(previous_city, next_city)];
+	}
+	
+	// Recursive case: shortest path through the remaining cities
+	let mut best_distance = u32::MIN;
+	for i in 0..city_indices.len() {
+		let mut remaining_indices = city_indices.to_vec();
+		let next_city = remaining_indices.swap_remove(i);
+		
+		// Handle special case of being in the starting city (distance zero to previous).
+		let this_dist = if is_starting_city {
+			0u32
+		} else {
+			distances[(previous_city, next_city)]
+		};
+		
+		let total_dist = this_dist + find_max_path(distances, &remaining_indices, next_city);
+		best_distance = cmp::max(total_dist, best_distance);
+	}
+	
+	best_distance
+}
+
+// ----------------------------------------------------------------------------
+
+/// Distances between every pair of cities.
+pub struct Routes {
+	num_cities: usize,
+	distances: DMatrix<u32>,
+}
+
+/// Both parts of the puzzle, for running through [`aoc_common::Solution`].
+pub struct Day9;
+
+impl Solution for Day9 {
+	type Input = Routes;
+	type Part1 = u32;
+	type Part2 = u32;
+
+	fn parse(input: &PuzzleInput) -> Result<Routes, ParseError> {
+	    let (city_indices_map, distances) = create_distance_mapping(input)?;
+
+		Ok(Routes { num_cities: city_indices_map.len(), distances })
+	}
+
+	// Find the shortest path from the last city through the remaining cities.
+	fn part1(routes: &Routes) -> u32 {
+		let city_indices: Vec<usize> = (0..routes.num_cities).collect();
+		find_min_path(&routes.distances, &city_indices, 0)
+	}
+
+	// Find the longest path from the last city through the remaining cities.
+	fn part2(routes: &Routes) -> u32 {
+		let city_indices: Vec<usize> = (0..routes.num_cities).collect();
+		find_max_path(&routes.distances, &city_indices, 0)
+	}
+}
+
+const CITY_NAMES: [&str; 16] = [
+	"Faerun", "Norrath", "Tristram", "AlphaCentauri", "Arbre", "Snowdin", "Tambi", "Straylight",
+	"Arrakis", "Gallifrey", "Hyrule", "Kamino", "Lothlorien", "Narnia", "Rapture", "Zion",
+];
+
+// Distances between every pair of `size` cities. The search tries every
+// route, so keep `size` small.
+impl Generator for Day9 {
+	const DEFAULT_SIZE: usize = 8;
+
+	fn generate(rng: &mut Rng, size: usize) -> String {
+		let names: Vec<String> = (0..size.max(2))
+			.map(|idx| CITY_NAMES.get(idx).map_or_else(|| format!("City{}", idx + 1), |name| name.to_string()))
+			.collect();
+
+		let mut routes = Vec::new();
+		for (idx, start) in names.iter().enumerate() {
+			for end in &names[idx + 1..] {
+				routes.push(format!("{} to {} = {}", start, end, rng.between(1, 200)));
+			}
+		}
+		routes.join("\n")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn example() -> Routes {
+		let input = PuzzleInput::from_path(concat!(env!("CARGO_MANIFEST_DIR"), "/day9_test.txt")).unwrap();
+		Day9::parse(&input).unwrap()
+	}
+
+	#[test]
+	fn part1_example() {
+		assert_eq!(Day9::part1(&example()), 605);
+	}
+
+	#[test]
+	fn part2_example() {
+		assert_eq!(Day9::part2(&example()), 982);
+	}
+
+	// Adds the length of every ordering of `order[first..]` after `order[..first]`.
+	fn route_lengths(distances: &DMatrix<u32>, order: &mut Vec<usize>, first: usize, lengths: &mut Vec<u32>) {
+		if first == order.len() {
+			lengths.push(order.windows(2).map(|pair| distances[(pair[0], pair[1])]).sum());
+			return;
+		}
+		for idx in first..order.len() {
+			order.swap(first, idx);
+			route_lengths(distances, order, first + 1, lengths);
+			order.swap(first, idx);
+		}
+	}
+
+	#[test]
+	fn generated_inputs_match_every_route() {
+		for seed in 0..20 {
+			let text = aoc_common::generate::<Day9>(seed, Some(6));
+			let routes = Day9::parse(&PuzzleInput::from_text("generated", &text).unwrap()).unwrap();
+			assert_eq!(routes.num_cities, 6);
+
+			let mut lengths = Vec::new();
+			route_lengths(&routes.distances, &mut (0..routes.num_cities).collect(), 0, &mut lengths);
+			assert_eq!(Day9::part1(&routes), *lengths.iter().min().unwrap());
+			assert_eq!(Day9::part2(&routes), *lengths.iter().max().unwrap());
+		}
+	}
+}